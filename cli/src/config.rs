@@ -0,0 +1,438 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use codebase_to_txt_core::models::{
+    BinaryPolicy, EncodingOverride, ExportConfig, LargeFileStrategy, LineEndingMode,
+    ManualSelectionState, OutputFormat, OverwritePolicy, SecretAction, SizeOverride, TokenizerKind,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+
+/// `ExportConfig` fields as flags. Flags override values loaded from `--config`.
+#[derive(Args, Debug, Default)]
+pub struct ConfigArgs {
+    /// JSON or TOML file with ExportConfig fields (camelCase keys, as sent by the desktop app)
+    #[arg(short, long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Directory to scan and export [default: config file rootPath, else "."]
+    #[arg(long, value_name = "DIR")]
+    pub root: Option<String>,
+
+    /// Apply .gitignore files
+    #[arg(long, overrides_with = "no_gitignore")]
+    pub gitignore: bool,
+
+    /// Do not apply .gitignore files, .git/info/exclude or the global git excludes file
+    #[arg(long)]
+    pub no_gitignore: bool,
+
+    /// Do not apply .ignore files
+    #[arg(long)]
+    pub no_dot_ignore: bool,
+
+    /// Do not apply .rgignore files
+    #[arg(long)]
+    pub no_rgignore: bool,
+
+    /// Do not apply the repository's .git/info/exclude
+    #[arg(long)]
+    pub no_git_exclude: bool,
+
+    /// Do not apply the global git excludes file (core.excludesFile)
+    #[arg(long)]
+    pub no_global_excludes: bool,
+
+    /// Include glob (repeatable; replaces includeGlobs from the config file)
+    #[arg(long = "include-glob", value_name = "GLOB")]
+    pub include_globs: Vec<String>,
+
+    /// Exclude glob (repeatable; replaces excludeGlobs from the config file)
+    #[arg(long = "exclude-glob", value_name = "GLOB")]
+    pub exclude_globs: Vec<String>,
+
+    /// Include extension such as ".rs" (repeatable)
+    #[arg(long = "include-ext", value_name = "EXT")]
+    pub include_extensions: Vec<String>,
+
+    /// Exclude extension such as ".png" (repeatable)
+    #[arg(long = "exclude-ext", value_name = "EXT")]
+    pub exclude_extensions: Vec<String>,
+
+    /// Hard-exclude entry; names match at any depth (repeatable; replaces the default list)
+    #[arg(long = "hard-exclude", value_name = "ENTRY")]
+    pub hard_excludes: Vec<String>,
+
+    /// Clear the hard-exclude list (.git stays excluded)
+    #[arg(long, conflicts_with = "hard_excludes")]
+    pub no_hard_excludes: bool,
+
+    /// Manually include a path, overriding rules (repeatable)
+    #[arg(long = "include", value_name = "PATH")]
+    pub include_paths: Vec<String>,
+
+    /// Manually exclude a path (repeatable)
+    #[arg(long = "exclude", value_name = "PATH")]
+    pub exclude_paths: Vec<String>,
+
+    /// Write only the structure section
+    #[arg(long)]
+    pub structure_only: bool,
+
+    /// Files above this size are handled by --large-file-strategy
+    #[arg(long = "max-file-size-kb", value_name = "KB")]
+    pub max_file_size_kb: Option<u64>,
+
+    /// truncate | head_tail | skip | keep
+    #[arg(long, value_name = "STRATEGY", value_parser = parse_enum::<LargeFileStrategy>)]
+    pub large_file_strategy: Option<LargeFileStrategy>,
+
+    /// Files with more lines than this are handled by --large-file-strategy too
+    #[arg(long, value_name = "LINES")]
+    pub max_file_lines: Option<u64>,
+
+    /// End head-only truncation on the last whole line
+    #[arg(long)]
+    pub truncate_at_line_boundary: bool,
+
+    /// Size limit and strategy for files matching GLOB, e.g. "*.json=20:skip", "*.rs=keep"
+    /// (repeatable, first match wins; replaces sizeOverrides from the config file)
+    #[arg(
+        long = "size-override",
+        value_name = "GLOB=[KB][:STRATEGY]",
+        value_parser = parse_size_override
+    )]
+    pub size_overrides: Vec<SizeOverride>,
+
+    /// txt | md
+    #[arg(long, value_name = "FORMAT", value_parser = parse_enum::<OutputFormat>)]
+    pub format: Option<OutputFormat>,
+
+    /// cl100k | o200k | heuristic
+    #[arg(long, value_name = "TOKENIZER", value_parser = parse_enum::<TokenizerKind>)]
+    pub tokenizer: Option<TokenizerKind>,
+
+    /// Drop or truncate low-priority files until the export fits this many tokens
+    #[arg(long, value_name = "TOKENS")]
+    pub token_budget: Option<u64>,
+
+    /// Glob protected from budget cuts, highest priority first (repeatable)
+    #[arg(long = "priority-glob", value_name = "GLOB")]
+    pub priority_globs: Vec<String>,
+
+    /// overwrite | fail_if_exists | auto_suffix (used when -o names an existing file)
+    #[arg(long, value_name = "POLICY", value_parser = parse_enum::<OverwritePolicy>)]
+    pub overwrite_policy: Option<OverwritePolicy>,
+
+    /// off | warn | redact | skip (files with likely credentials)
+    #[arg(long, value_name = "ACTION", value_parser = parse_enum::<SecretAction>)]
+    pub secret_action: Option<SecretAction>,
+
+    /// Read files matching GLOB as ENCODING, e.g. "legacy/**=shift_jis" (repeatable, first match
+    /// wins; replaces encodingOverrides from the config file)
+    #[arg(long = "encoding", value_name = "GLOB=ENCODING", value_parser = parse_encoding_override)]
+    pub encoding_overrides: Vec<EncodingOverride>,
+
+    /// normalize | annotate | preserve (annotate notes each file whose endings were converted)
+    #[arg(long, value_name = "MODE", value_parser = parse_enum::<LineEndingMode>)]
+    pub line_endings: Option<LineEndingMode>,
+
+    /// skip | list | metadata | hex | base64 (what to write for binary files; PNG, JPEG, GIF and
+    /// WebP images also get their dimensions, and a metadata stub when skipped)
+    #[arg(long, value_name = "POLICY", value_parser = parse_enum::<BinaryPolicy>)]
+    pub binary_policy: Option<BinaryPolicy>,
+
+    /// Binary files above this size get metadata instead of a hex or base64 dump
+    #[arg(long = "binary-dump-max-kb", value_name = "KB")]
+    pub binary_dump_max_kb: Option<u64>,
+}
+
+impl ConfigArgs {
+    pub fn resolve(&self) -> Result<ExportConfig, String> {
+        let mut base = default_config_value();
+        if let Some(path) = &self.config {
+            let file_value = load_config_file(path)?;
+            merge_objects(&mut base, file_value);
+        }
+        let mut config: ExportConfig =
+            serde_json::from_value(base).map_err(|e| format!("Invalid export config: {e}"))?;
+
+        if let Some(root) = &self.root {
+            config.root_path = root.clone();
+        }
+        if self.gitignore {
+            config.use_gitignore = true;
+        }
+        if self.no_gitignore {
+            config.use_gitignore = false;
+        }
+        let sources = &mut config.ignore_sources;
+        sources.dot_ignore &= !self.no_dot_ignore;
+        sources.rgignore &= !self.no_rgignore;
+        sources.git_info_exclude &= !self.no_git_exclude;
+        sources.global_excludes &= !self.no_global_excludes;
+        replace_if_set(&mut config.include_globs, &self.include_globs);
+        replace_if_set(&mut config.exclude_globs, &self.exclude_globs);
+        replace_if_set(&mut config.include_extensions, &self.include_extensions);
+        replace_if_set(&mut config.exclude_extensions, &self.exclude_extensions);
+        replace_if_set(&mut config.priority_globs, &self.priority_globs);
+        replace_if_set(&mut config.hard_excludes, &self.hard_excludes);
+        if self.no_hard_excludes {
+            config.hard_excludes.clear();
+        }
+        for path in &self.include_paths {
+            config
+                .manual_selections
+                .insert(path.clone(), ManualSelectionState::Include);
+        }
+        for path in &self.exclude_paths {
+            config
+                .manual_selections
+                .insert(path.clone(), ManualSelectionState::Exclude);
+        }
+        if self.structure_only {
+            config.structure_only = true;
+        }
+        if let Some(max_file_size_kb) = self.max_file_size_kb {
+            config.max_file_size_kb = max_file_size_kb;
+        }
+        if let Some(strategy) = &self.large_file_strategy {
+            config.large_file_strategy = strategy.clone();
+        }
+        if self.max_file_lines.is_some() {
+            config.max_file_lines = self.max_file_lines;
+        }
+        if self.truncate_at_line_boundary {
+            config.truncate_at_line_boundary = true;
+        }
+        if !self.size_overrides.is_empty() {
+            config.size_overrides = self.size_overrides.clone();
+        }
+        if let Some(format) = self.format {
+            config.output_format = format;
+        }
+        if let Some(tokenizer) = self.tokenizer {
+            config.tokenizer = tokenizer;
+        }
+        if self.token_budget.is_some() {
+            config.token_budget = self.token_budget;
+        }
+        if let Some(policy) = self.overwrite_policy {
+            config.overwrite_policy = policy;
+        }
+        if let Some(action) = self.secret_action {
+            config.secret_action = action;
+        }
+        if !self.encoding_overrides.is_empty() {
+            config.encoding_overrides = self.encoding_overrides.clone();
+        }
+        if let Some(mode) = self.line_endings {
+            config.line_endings = mode;
+        }
+        if let Some(policy) = self.binary_policy {
+            config.binary_policy = policy;
+        }
+        if let Some(binary_dump_max_kb) = self.binary_dump_max_kb {
+            config.binary_dump_max_kb = binary_dump_max_kb;
+        }
+        Ok(config)
+    }
+}
+
+/// Same defaults as the desktop app's `defaultExportConfig`, rooted at the working directory.
+fn default_config_value() -> Value {
+    json!({
+        "rootPath": ".",
+        "useGitignore": true,
+        "includeGlobs": [],
+        "excludeGlobs": [],
+        "includeExtensions": [],
+        "excludeExtensions": [],
+        "structureOnly": false,
+        "maxFileSizeKB": 256,
+        "largeFileStrategy": "truncate",
+        "manualSelections": {},
+        "outputFormat": "txt"
+    })
+}
+
+fn load_config_file(path: &Path) -> Result<Map<String, Value>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file '{}': {e}", path.display()))?;
+    let is_toml = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("toml"))
+        .unwrap_or(false);
+    let value: Value = if is_toml {
+        toml::from_str(&text).map_err(|e| format!("Invalid TOML in '{}': {e}", path.display()))?
+    } else {
+        serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid JSON in '{}': {e}", path.display()))?
+    };
+    let Value::Object(mut value) = value else {
+        return Err(format!(
+            "Config file '{}' must contain an object",
+            path.display()
+        ));
+    };
+
+    // A relative rootPath in a config file is relative to the file, not the working directory.
+    if let Some(Value::String(root)) = value.get_mut("rootPath") {
+        if Path::new(root.as_str()).is_relative() {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                *root = parent.join(root.as_str()).to_string_lossy().to_string();
+            }
+        }
+    }
+    Ok(value)
+}
+
+fn merge_objects(base: &mut Value, overlay: Map<String, Value>) {
+    if let Value::Object(base_map) = base {
+        base_map.extend(overlay);
+    }
+}
+
+fn replace_if_set(target: &mut Vec<String>, values: &[String]) {
+    if !values.is_empty() {
+        *target = values.to_vec();
+    }
+}
+
+fn parse_enum<T: DeserializeOwned>(raw: &str) -> Result<T, String> {
+    serde_json::from_value(Value::String(raw.to_string())).map_err(|e| e.to_string())
+}
+
+fn parse_encoding_override(raw: &str) -> Result<EncodingOverride, String> {
+    match raw.rsplit_once('=') {
+        Some((glob, encoding)) if !glob.is_empty() && !encoding.is_empty() => {
+            Ok(EncodingOverride {
+                glob: glob.to_string(),
+                encoding: encoding.to_string(),
+            })
+        }
+        _ => Err("expected GLOB=ENCODING".to_string()),
+    }
+}
+
+fn parse_size_override(raw: &str) -> Result<SizeOverride, String> {
+    let usage = || "expected GLOB=[KB][:STRATEGY]".to_string();
+    let (glob, spec) = raw
+        .rsplit_once('=')
+        .filter(|(glob, _)| !glob.is_empty())
+        .ok_or_else(usage)?;
+    let mut entry = SizeOverride {
+        glob: glob.to_string(),
+        max_file_size_kb: None,
+        max_file_lines: None,
+        large_file_strategy: None,
+    };
+    for part in spec.split(':').filter(|part| !part.is_empty()) {
+        match part.parse::<u64>() {
+            Ok(kb) => entry.max_file_size_kb = Some(kb),
+            Err(_) => entry.large_file_strategy = Some(parse_enum(part)?),
+        }
+    }
+    if entry.max_file_size_kb.is_none() && entry.large_file_strategy.is_none() {
+        return Err(usage());
+    }
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use codebase_to_txt_core::models::{LargeFileStrategy, ManualSelectionState, OutputFormat};
+    use tempfile::tempdir;
+
+    use super::{parse_size_override, ConfigArgs};
+
+    #[test]
+    fn defaults_match_the_desktop_app() {
+        let config = ConfigArgs::default().resolve().unwrap();
+        assert_eq!(config.root_path, ".");
+        assert!(config.use_gitignore);
+        assert_eq!(config.max_file_size_kb, 256);
+        assert!(matches!(
+            config.large_file_strategy,
+            LargeFileStrategy::Truncate
+        ));
+        assert_eq!(config.output_format, OutputFormat::Txt);
+        assert!(config
+            .hard_excludes
+            .iter()
+            .any(|entry| entry == "node_modules"));
+    }
+
+    #[test]
+    fn flags_override_toml_config_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("export.toml");
+        fs::write(
+            &config_path,
+            "rootPath = \"repo\"\nuseGitignore = false\nexcludeGlobs = [\"dist/**\"]\nmaxFileSizeKB = 64\noutputFormat = \"md\"\n",
+        )
+        .unwrap();
+
+        let args = ConfigArgs {
+            config: Some(config_path),
+            max_file_size_kb: Some(8),
+            no_rgignore: true,
+            include_paths: vec!["dist/keep.js".to_string()],
+            ..ConfigArgs::default()
+        };
+        let config = args.resolve().unwrap();
+
+        assert_eq!(config.root_path, dir.path().join("repo").to_string_lossy());
+        assert!(!config.use_gitignore);
+        assert!(!config.ignore_sources.rgignore);
+        assert!(config.ignore_sources.dot_ignore);
+        assert_eq!(config.exclude_globs, vec!["dist/**"]);
+        assert_eq!(config.max_file_size_kb, 8);
+        assert_eq!(config.output_format, OutputFormat::Md);
+        assert!(matches!(
+            config.manual_selections.get("dist/keep.js"),
+            Some(ManualSelectionState::Include)
+        ));
+    }
+
+    #[test]
+    fn json_config_accepts_desktop_app_payload() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("export.json");
+        fs::write(
+            &config_path,
+            r#"{"rootPath": "/abs/repo", "largeFileStrategy": "skip", "tokenBudget": 1000}"#,
+        )
+        .unwrap();
+
+        let args = ConfigArgs {
+            config: Some(config_path),
+            ..ConfigArgs::default()
+        };
+        let config = args.resolve().unwrap();
+
+        assert_eq!(config.root_path, "/abs/repo");
+        assert!(matches!(
+            config.large_file_strategy,
+            LargeFileStrategy::Skip
+        ));
+        assert_eq!(config.token_budget, Some(1000));
+    }
+
+    #[test]
+    fn size_override_flag_takes_a_limit_a_strategy_or_both() {
+        let entry = parse_size_override("fixtures/*.json=20:skip").unwrap();
+        assert_eq!(entry.glob, "fixtures/*.json");
+        assert_eq!(entry.max_file_size_kb, Some(20));
+        assert_eq!(entry.large_file_strategy, Some(LargeFileStrategy::Skip));
+
+        let keep = parse_size_override("*.rs=keep").unwrap();
+        assert_eq!(keep.max_file_size_kb, None);
+        assert_eq!(keep.large_file_strategy, Some(LargeFileStrategy::Keep));
+        assert!(parse_size_override("*.rs=").is_err());
+        assert!(parse_size_override("*.rs=huge").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+mod config;
+
+use std::error::Error;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use codebase_to_txt_core::models::ScanLimits;
+use codebase_to_txt_core::{
+    evaluate_selection, explain_path, export_to_writer, preview_export, run_export, scan_children,
+    scan_root,
+};
+use serde::Serialize;
+
+use config::ConfigArgs;
+
+const STDOUT_TARGET: &str = "-";
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "codebase-to-txt",
+    version,
+    about = "Scan, preview and export a codebase into a single text or Markdown file"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print one level of the directory tree with rule decisions as JSON
+    Scan {
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// Directory under the root to list instead of the root itself
+        #[arg(long, value_name = "PATH")]
+        dir: Option<String>,
+    },
+    /// Print the selection summary as JSON
+    Evaluate {
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Print which rule includes or excludes a path, as JSON
+    Explain {
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// File or directory, relative to the root
+        path: String,
+    },
+    /// Print the export size and token estimate as JSON
+    Preview {
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Write the export to a file, or to stdout with "-o -"
+    Export {
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// Output file path, or "-" for stdout
+        #[arg(short, long, value_name = "FILE", default_value = STDOUT_TARGET)]
+        output: String,
+        /// Allow exporting manually included sensitive files (.env, *.pem, ...)
+        #[arg(long)]
+        confirm_sensitive: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    let limits = ScanLimits::default();
+    match command {
+        Command::Scan { config, dir } => {
+            let config = config.resolve()?;
+            match dir {
+                Some(dir) => {
+                    let batch = scan_children(&config, &dir, &limits)?;
+                    for warning in &batch.warnings {
+                        eprintln!("warning: {warning}");
+                    }
+                    print_json(&batch.nodes)
+                }
+                None => {
+                    let tree = scan_root(&config, &limits)?;
+                    for warning in &tree.warnings {
+                        eprintln!("warning: {warning}");
+                    }
+                    print_json(&tree.root)
+                }
+            }
+        }
+        Command::Evaluate { config } => {
+            let config = config.resolve()?;
+            print_json(&evaluate_selection(&config, &limits)?)
+        }
+        Command::Explain { config, path } => {
+            let config = config.resolve()?;
+            print_json(&explain_path(&config, &path)?)
+        }
+        Command::Preview { config } => {
+            let config = config.resolve()?;
+            print_json(&preview_export(&config, &limits)?)
+        }
+        Command::Export {
+            config,
+            output,
+            confirm_sensitive,
+        } => {
+            let config = config.resolve()?;
+            if output == STDOUT_TARGET {
+                let result = export_to_writer(
+                    &config,
+                    io::stdout().lock(),
+                    STDOUT_TARGET,
+                    &limits,
+                    confirm_sensitive,
+                )?;
+                for note in &result.notes {
+                    eprintln!("note: {note}");
+                }
+                Ok(())
+            } else {
+                print_json(&run_export(&config, &output, &limits, confirm_sensitive)?)
+            }
+        }
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    let text = serde_json::to_string_pretty(value)?;
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{text}").map_err(|e| format!("Failed to write to stdout: {e}"))?;
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::application::selection::SelectedFile;
+
+const SIGNATURE_PROBE_BYTES: usize = 64;
+const TXT_SIGNATURE: &[u8] = b"=== STRUCTURE ===\n";
+const MD_SIGNATURE: &[u8] = b"## Structure\n\n";
+const MD_SIGNATURE_NEXT: [&[u8]; 3] = [b"- `", b"## File: `", b"\n"];
+
+enum ArtifactKind {
+    CurrentOutput,
+    StagingFile,
+    PreviousExport,
+}
+
+/// Recognizes files the exporter itself produced: the file being written to, staging files of
+/// in-flight exports, and earlier exports recognized by their structure header.
+pub struct ArtifactFilter {
+    output_path: Option<PathBuf>,
+}
+
+impl ArtifactFilter {
+    pub fn new(output_path: Option<&Path>) -> Self {
+        Self {
+            output_path: output_path.and_then(resolve_output_path),
+        }
+    }
+
+    /// The warning to report when `file` has to be left out of the export.
+    pub fn exclusion(&self, file: &SelectedFile) -> Option<String> {
+        let reason = match classify(file, self.output_path.as_deref())? {
+            ArtifactKind::CurrentOutput => "it is this export's output file",
+            ArtifactKind::StagingFile => "it is the temporary file of an export in progress",
+            ArtifactKind::PreviousExport => "it is the output of a previous export",
+        };
+        Some(format!(
+            "Excluded '{}' from the export: {reason}",
+            file.rel_path
+        ))
+    }
+}
+
+/// The output file may not exist yet, so only its directory can be canonicalized.
+fn resolve_output_path(path: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?;
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let parent = parent.canonicalize().ok()?;
+    Some(parent.join(file_name))
+}
+
+fn classify(file: &SelectedFile, output_path: Option<&Path>) -> Option<ArtifactKind> {
+    if output_path == Some(file.abs_path.as_path()) {
+        return Some(ArtifactKind::CurrentOutput);
+    }
+    let name = file.abs_path.file_name()?.to_string_lossy();
+    if is_staging_name(&name) {
+        return Some(ArtifactKind::StagingFile);
+    }
+    if has_export_extension(&name) && starts_with_export_header(&file.abs_path) {
+        return Some(ArtifactKind::PreviousExport);
+    }
+    None
+}
+
+/// Staging files are named `.<output name>.<random>.tmp`.
+fn is_staging_name(name: &str) -> bool {
+    let Some(inner) = name
+        .strip_prefix('.')
+        .and_then(|rest| rest.strip_suffix(".tmp"))
+    else {
+        return false;
+    };
+    match inner.rsplit_once('.') {
+        Some((output_name, random)) => {
+            has_export_extension(output_name)
+                && !random.is_empty()
+                && random.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+fn has_export_extension(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.ends_with(".txt") || lower.ends_with(".md")
+}
+
+fn starts_with_export_header(path: &Path) -> bool {
+    let mut probe = Vec::with_capacity(SIGNATURE_PROBE_BYTES);
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    if file
+        .take(SIGNATURE_PROBE_BYTES as u64)
+        .read_to_end(&mut probe)
+        .is_err()
+    {
+        return false;
+    }
+    if probe.starts_with(TXT_SIGNATURE) {
+        return true;
+    }
+    match probe.strip_prefix(MD_SIGNATURE) {
+        Some(rest) => MD_SIGNATURE_NEXT.iter().any(|next| rest.starts_with(next)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::tempdir;
+
+    use crate::application::selection::SelectedFile;
+
+    use super::{is_staging_name, ArtifactFilter};
+
+    fn selected(root: &Path, rel_path: &str) -> SelectedFile {
+        let abs_path = root.join(rel_path);
+        SelectedFile {
+            size: fs::metadata(&abs_path).unwrap().len(),
+            abs_path,
+            rel_path: rel_path.to_string(),
+            byte_limit: None,
+            sensitive: false,
+            size_override: None,
+        }
+    }
+
+    #[test]
+    fn excludes_output_staging_and_previous_exports() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        fs::write(
+            root_path.join("notes.md"),
+            "## Structure\n\nPlanned layout of the repo.\n",
+        )
+        .unwrap();
+        fs::write(root_path.join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root_path.join("codebase.txt"), "stale").unwrap();
+        fs::write(root_path.join("old.txt"), "=== STRUCTURE ===\nmain.rs\n").unwrap();
+        fs::write(root_path.join("old.md"), "## Structure\n\n- `main.rs`\n").unwrap();
+        fs::write(root_path.join(".codebase.txt.a1B2c3.tmp"), "").unwrap();
+
+        let names = [
+            ".codebase.txt.a1B2c3.tmp",
+            "codebase.txt",
+            "main.rs",
+            "notes.md",
+            "old.md",
+            "old.txt",
+        ];
+        let filter = ArtifactFilter::new(Some(&root.path().join("codebase.txt")));
+        let warnings: Vec<Option<String>> = names
+            .iter()
+            .map(|name| filter.exclusion(&selected(&root_path, name)))
+            .collect();
+
+        let kept: Vec<&str> = names
+            .iter()
+            .zip(&warnings)
+            .filter(|(_, warning)| warning.is_none())
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(kept, vec!["main.rs", "notes.md"]);
+        let reason = warnings[1].as_deref().unwrap();
+        assert!(reason.contains("'codebase.txt'"));
+        assert!(reason.contains("this export's output file"));
+    }
+
+    #[test]
+    fn staging_names_require_export_extension_and_random_part() {
+        assert!(is_staging_name(".codebase.md.XyZ123.tmp"));
+        assert!(!is_staging_name(".cache.tmp"));
+        assert!(!is_staging_name(".data.bin.XyZ123.tmp"));
+        assert!(!is_staging_name("codebase.txt.XyZ123.tmp"));
+    }
+}
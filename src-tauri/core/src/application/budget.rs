@@ -0,0 +1,253 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::application::selection::SelectedFile;
+use crate::infrastructure::errors::{AppError, AppResult};
+use crate::models::{BudgetAction, BudgetCut, PathTokenCount};
+
+const MIN_TRUNCATED_TOKENS: u64 = 256;
+
+pub struct FilePriority {
+    globs: Option<GlobSet>,
+    glob_count: usize,
+}
+
+impl FilePriority {
+    pub fn from_globs(patterns: &[String]) -> AppResult<Self> {
+        if patterns.is_empty() {
+            return Ok(Self {
+                globs: None,
+                glob_count: 0,
+            });
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern).map_err(|e| AppError::RuleInvalidGlob {
+                glob: Some(pattern.clone()),
+                reason: e.kind().to_string(),
+            })?;
+            builder.add(glob);
+        }
+        let set = builder.build().map_err(|e| AppError::RuleInvalidGlob {
+            glob: None,
+            reason: e.to_string(),
+        })?;
+        Ok(Self {
+            globs: Some(set),
+            glob_count: patterns.len(),
+        })
+    }
+
+    /// Index of the first matching priority glob, or `None` when no glob matches.
+    fn rank(&self, rel_path: &str) -> Option<usize> {
+        self.globs
+            .as_ref()
+            .and_then(|set| set.matches(rel_path).into_iter().min())
+    }
+
+    /// Larger keys are cut first: unranked before ranked, tests before sources,
+    /// deeper before shallower, larger before smaller.
+    fn cut_key(&self, file: &SelectedFile) -> (usize, bool, usize, u64) {
+        (
+            self.rank(&file.rel_path).unwrap_or(self.glob_count),
+            is_test_path(&file.rel_path),
+            path_depth(&file.rel_path),
+            file.size,
+        )
+    }
+
+    fn describe(&self, file: &SelectedFile, tokens: u64) -> String {
+        let rank = match self.rank(&file.rel_path) {
+            Some(index) => format!("priority glob #{}", index + 1),
+            None if self.globs.is_some() => "no priority glob match".to_string(),
+            None => "no priority globs".to_string(),
+        };
+        let kind = if is_test_path(&file.rel_path) {
+            "test file"
+        } else {
+            "source file"
+        };
+        format!(
+            "{rank}, {kind}, depth {}, {} bytes, ~{tokens} tokens",
+            path_depth(&file.rel_path),
+            file.size
+        )
+    }
+}
+
+/// Drops or truncates the lowest-priority files until `total_tokens` fits in `budget`.
+/// Token counts are per exported file section; files without a count are left alone.
+pub fn plan_cuts(
+    files: &mut Vec<SelectedFile>,
+    file_tokens: &[PathTokenCount],
+    total_tokens: u64,
+    budget: u64,
+    priority: &FilePriority,
+) -> Vec<BudgetCut> {
+    let tokens_by_path: HashMap<&str, u64> = file_tokens
+        .iter()
+        .map(|item| (item.path.as_str(), item.tokens))
+        .collect();
+
+    let mut candidates: Vec<usize> = (0..files.len())
+        .filter(|index| {
+            tokens_by_path
+                .get(files[*index].rel_path.as_str())
+                .copied()
+                .unwrap_or(0)
+                > 0
+        })
+        .collect();
+    candidates.sort_by_key(|index| {
+        let file = &files[*index];
+        Reverse((priority.cut_key(file), file.rel_path.clone()))
+    });
+
+    let mut cuts = Vec::new();
+    let mut dropped = vec![false; files.len()];
+    let mut remaining = total_tokens;
+
+    for index in candidates {
+        if remaining <= budget {
+            break;
+        }
+        let file = &mut files[index];
+        let tokens = tokens_by_path[file.rel_path.as_str()];
+        let overshoot = remaining - budget;
+        let reason = priority.describe(file, tokens);
+
+        let keep_tokens = tokens.saturating_sub(overshoot);
+        if keep_tokens >= MIN_TRUNCATED_TOKENS {
+            let current_bytes = file.byte_limit.unwrap_or(file.size).min(file.size);
+            let keep_bytes = current_bytes.saturating_mul(keep_tokens) / tokens;
+            file.byte_limit = Some(keep_bytes);
+            cuts.push(BudgetCut {
+                path: file.rel_path.clone(),
+                action: BudgetAction::Truncated,
+                tokens_before: tokens,
+                tokens_after: keep_tokens,
+                reason,
+            });
+            remaining -= overshoot;
+        } else {
+            dropped[index] = true;
+            cuts.push(BudgetCut {
+                path: file.rel_path.clone(),
+                action: BudgetAction::Dropped,
+                tokens_before: tokens,
+                tokens_after: 0,
+                reason,
+            });
+            remaining -= tokens;
+        }
+    }
+
+    let mut index = 0usize;
+    files.retain(|_| {
+        let keep = !dropped[index];
+        index += 1;
+        keep
+    });
+    cuts
+}
+
+fn is_test_path(rel_path: &str) -> bool {
+    let lower = rel_path.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+    lower.split('/').rev().skip(1).any(|dir| {
+        matches!(
+            dir,
+            "test" | "tests" | "__tests__" | "spec" | "specs" | "testdata" | "fixtures"
+        )
+    }) || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || file_name.contains("_test.")
+        || file_name.starts_with("test_")
+}
+
+fn path_depth(rel_path: &str) -> usize {
+    rel_path.matches('/').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::application::selection::SelectedFile;
+    use crate::models::{BudgetAction, PathTokenCount};
+
+    use super::{plan_cuts, FilePriority};
+
+    fn selected(rel_path: &str, size: u64) -> SelectedFile {
+        SelectedFile {
+            abs_path: PathBuf::from(rel_path),
+            rel_path: rel_path.to_string(),
+            size,
+            byte_limit: None,
+            sensitive: false,
+            size_override: None,
+        }
+    }
+
+    fn tokens(path: &str, tokens: u64) -> PathTokenCount {
+        PathTokenCount {
+            path: path.to_string(),
+            tokens,
+        }
+    }
+
+    #[test]
+    fn drops_tests_and_deep_files_before_shallow_sources() {
+        let mut files = vec![
+            selected("src/lib.rs", 100),
+            selected("src/deep/nested/util.rs", 100),
+            selected("tests/lib_test.rs", 100),
+        ];
+        let counts = vec![
+            tokens("src/lib.rs", 100),
+            tokens("src/deep/nested/util.rs", 100),
+            tokens("tests/lib_test.rs", 100),
+        ];
+        let priority = FilePriority::from_globs(&[]).unwrap();
+
+        let cuts = plan_cuts(&mut files, &counts, 320, 150, &priority);
+
+        let cut_paths: Vec<&str> = cuts.iter().map(|cut| cut.path.as_str()).collect();
+        assert_eq!(
+            cut_paths,
+            vec!["tests/lib_test.rs", "src/deep/nested/util.rs"]
+        );
+        assert!(cuts.iter().all(|cut| cut.action == BudgetAction::Dropped));
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].rel_path, "src/lib.rs");
+    }
+
+    #[test]
+    fn priority_globs_protect_matching_files() {
+        let mut files = vec![selected("docs/guide.md", 10), selected("src/core.rs", 10)];
+        let counts = vec![tokens("docs/guide.md", 100), tokens("src/core.rs", 100)];
+        let priority = FilePriority::from_globs(&["docs/**".to_string()]).unwrap();
+
+        let cuts = plan_cuts(&mut files, &counts, 200, 120, &priority);
+
+        assert_eq!(cuts.len(), 1);
+        assert_eq!(cuts[0].path, "src/core.rs");
+        assert!(cuts[0].reason.contains("no priority glob match"));
+    }
+
+    #[test]
+    fn truncates_marginal_file_when_enough_of_it_fits() {
+        let mut files = vec![selected("src/big.rs", 40_000)];
+        let counts = vec![tokens("src/big.rs", 10_000)];
+        let priority = FilePriority::from_globs(&[]).unwrap();
+
+        let cuts = plan_cuts(&mut files, &counts, 10_050, 5_050, &priority);
+
+        assert_eq!(cuts.len(), 1);
+        assert_eq!(cuts[0].action, BudgetAction::Truncated);
+        assert_eq!(cuts[0].tokens_after, 5_000);
+        assert_eq!(files[0].byte_limit, Some(20_000));
+    }
+}
@@ -0,0 +1,403 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use crate::infrastructure::sorting::compare_names;
+use crate::models::OutputFormat;
+
+const MIN_FENCE_LEN: usize = 3;
+
+/// Where the written head of a truncated file ends: a byte limit, or a whole number of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruncatedAt {
+    Bytes(u64),
+    Lines(u64),
+}
+
+impl fmt::Display for TruncatedAt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TruncatedAt::Bytes(bytes) => write!(f, "{bytes} bytes"),
+            TruncatedAt::Lines(lines) => write!(f, "{lines} lines"),
+        }
+    }
+}
+
+pub struct Layout {
+    format: OutputFormat,
+}
+
+impl Layout {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn uses_fences(&self) -> bool {
+        matches!(self.format, OutputFormat::Md)
+    }
+
+    pub fn structure(&self) -> StructureLines {
+        StructureLines {
+            format: self.format,
+            files: Vec::new(),
+        }
+    }
+
+    pub fn file_header_lines(&self, rel_path: &str, fence: &str) -> Vec<String> {
+        match self.format {
+            OutputFormat::Txt => vec![format!("=== FILE: {rel_path} ===")],
+            OutputFormat::Md => vec![
+                format!("## File: {}", inline_code(rel_path)),
+                String::new(),
+                format!("{fence}{}", language_for_path(rel_path)),
+            ],
+        }
+    }
+
+    /// Whether a newline has to be written between the file content and the footer.
+    pub fn needs_content_terminator(&self, wrote_content: bool, ended_with_newline: bool) -> bool {
+        match self.format {
+            OutputFormat::Txt => true,
+            OutputFormat::Md => wrote_content && !ended_with_newline,
+        }
+    }
+
+    pub fn file_footer_lines(
+        &self,
+        rel_path: &str,
+        fence: &str,
+        truncated_at: Option<TruncatedAt>,
+    ) -> Vec<String> {
+        let mut lines = Vec::new();
+        match self.format {
+            OutputFormat::Txt => {
+                if let Some(limit) = truncated_at {
+                    lines.push(format!("[TRUNCATED at {limit}]"));
+                }
+                lines.push(format!("=== END FILE: {rel_path} ==="));
+            }
+            OutputFormat::Md => {
+                lines.push(fence.to_string());
+                if let Some(limit) = truncated_at {
+                    lines.push(String::new());
+                    lines.push(format!("> [TRUNCATED at {limit}]"));
+                }
+            }
+        }
+        lines.push(String::new());
+        lines
+    }
+}
+
+/// The line standing in for the middle of a file cut to its head and tail; the same in every
+/// format, so it reads as part of the file content.
+pub fn elision_marker(omitted_lines: u64) -> String {
+    format!("[... {omitted_lines} lines omitted ...]")
+}
+
+/// Returns a backtick fence that is longer than any backtick run in the content.
+pub fn fence_for(longest_backtick_run: usize) -> String {
+    "`".repeat(usize::max(MIN_FENCE_LEN, longest_backtick_run + 1))
+}
+
+pub fn language_for_path(rel_path: &str) -> &'static str {
+    let file_name = rel_path.rsplit('/').next().unwrap_or(rel_path);
+    let lower_name = file_name.to_lowercase();
+    match lower_name.as_str() {
+        "dockerfile" => return "dockerfile",
+        "makefile" | "gnumakefile" => return "makefile",
+        "cmakelists.txt" => return "cmake",
+        _ => {}
+    }
+
+    let Some((_, ext)) = lower_name.rsplit_once('.') else {
+        return "";
+    };
+    match ext {
+        "rs" => "rust",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "py" | "pyi" => "python",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "scala" => "scala",
+        "dart" => "dart",
+        "lua" => "lua",
+        "sh" | "bash" | "zsh" => "bash",
+        "ps1" | "psm1" => "powershell",
+        "bat" | "cmd" => "batch",
+        "sql" => "sql",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "less" => "less",
+        "vue" => "vue",
+        "svelte" => "svelte",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "xml" | "svg" => "xml",
+        "md" | "markdown" => "markdown",
+        "graphql" | "gql" => "graphql",
+        "proto" => "protobuf",
+        "ini" | "cfg" => "ini",
+        _ => "",
+    }
+}
+
+fn inline_code(text: &str) -> String {
+    let longest = longest_run(text.as_bytes(), b'`');
+    let ticks = "`".repeat(longest + 1);
+    if longest == 0 {
+        format!("{ticks}{text}{ticks}")
+    } else {
+        format!("{ticks} {text} {ticks}")
+    }
+}
+
+fn longest_run(bytes: &[u8], needle: u8) -> usize {
+    let mut longest = 0usize;
+    let mut current = 0usize;
+    for byte in bytes {
+        if *byte == needle {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// Collects the exported files as they are written and lists them once the last one is known,
+/// directories first, names compared case-insensitively. That order differs from the walk's, so
+/// this holds one entry per selected file: the one part of an export that grows with the repo.
+pub struct StructureLines {
+    format: OutputFormat,
+    files: Vec<StructureFile>,
+}
+
+struct StructureFile {
+    rel_path: String,
+    /// Shown after the file name, e.g. the size and type of a binary file.
+    detail: Option<String>,
+}
+
+impl StructureLines {
+    pub fn add_file(&mut self, rel_path: &str, detail: Option<&str>) {
+        self.files.push(StructureFile {
+            rel_path: rel_path.to_string(),
+            detail: detail.map(str::to_string),
+        });
+    }
+
+    pub fn into_lines(self) -> Vec<String> {
+        match self.format {
+            OutputFormat::Txt => {
+                let mut lines = vec!["=== STRUCTURE ===".to_string()];
+                lines.extend(flat_structure_lines(&self.files));
+                lines.push(String::new());
+                lines
+            }
+            OutputFormat::Md => {
+                let mut lines = vec!["## Structure".to_string(), String::new()];
+                lines.extend(nested_structure_lines(&self.files));
+                lines.push(String::new());
+                lines
+            }
+        }
+    }
+}
+
+fn flat_structure_lines(files: &[StructureFile]) -> Vec<String> {
+    struct StructureEntry {
+        path: String,
+        is_dir: bool,
+        detail: Option<String>,
+    }
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    seen.insert(".".to_string());
+    entries.push(StructureEntry {
+        path: ".".to_string(),
+        is_dir: true,
+        detail: None,
+    });
+
+    for file in files {
+        let parts = file.rel_path.split('/').collect::<Vec<_>>();
+        let mut current = String::new();
+        for (index, part) in parts.iter().enumerate() {
+            if !current.is_empty() {
+                current.push('/');
+            }
+            current.push_str(part);
+            if seen.insert(current.clone()) {
+                let is_dir = index + 1 != parts.len();
+                entries.push(StructureEntry {
+                    path: current.clone(),
+                    is_dir,
+                    detail: if is_dir { None } else { file.detail.clone() },
+                });
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => compare_names(&a.path, &b.path),
+    });
+
+    entries
+        .into_iter()
+        .map(|entry| match entry.detail {
+            Some(detail) => format!("{} [{detail}]", entry.path),
+            None => entry.path,
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct DirNode<'a> {
+    dirs: BTreeMap<&'a str, DirNode<'a>>,
+    files: Vec<(&'a str, Option<&'a str>)>,
+}
+
+fn nested_structure_lines(files: &[StructureFile]) -> Vec<String> {
+    let mut root = DirNode::default();
+    for file in files {
+        let mut parts = file.rel_path.split('/').peekable();
+        let mut node = &mut root;
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                node.files.push((part, file.detail.as_deref()));
+            } else {
+                node = node.dirs.entry(part).or_default();
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    push_nested_lines(&root, 0, &mut lines);
+    lines
+}
+
+fn push_nested_lines(node: &DirNode<'_>, depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+
+    let mut dirs = node.dirs.iter().collect::<Vec<_>>();
+    dirs.sort_by(|(a, _), (b, _)| compare_names(a, b));
+    for (name, child) in dirs {
+        lines.push(format!("{indent}- {}", inline_code(&format!("{name}/"))));
+        push_nested_lines(child, depth + 1, lines);
+    }
+
+    let mut files = node.files.iter().collect::<Vec<_>>();
+    files.sort_by(|(a, _), (b, _)| compare_names(a, b));
+    for (name, detail) in files {
+        let mut line = format!("{indent}- {}", inline_code(name));
+        if let Some(detail) = detail {
+            line.push_str(&format!(" ({detail})"));
+        }
+        lines.push(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::OutputFormat;
+
+    use super::{fence_for, inline_code, language_for_path, Layout};
+
+    fn structure_lines(format: OutputFormat, files: &[&str]) -> Vec<String> {
+        let mut structure = Layout::new(format).structure();
+        for file in files {
+            let detail = file
+                .ends_with(".png")
+                .then_some("binary, 68 bytes, image/png");
+            structure.add_file(file, detail);
+        }
+        structure.into_lines()
+    }
+
+    #[test]
+    fn markdown_structure_is_a_nested_list_with_directories_first() {
+        let files = ["logo.png", "README.md", "src/app/mod.rs", "src/main.rs"];
+        let lines = structure_lines(OutputFormat::Md, &files);
+        assert_eq!(
+            lines,
+            vec![
+                "## Structure",
+                "",
+                "- `src/`",
+                "  - `app/`",
+                "    - `mod.rs`",
+                "  - `main.rs`",
+                "- `logo.png` (binary, 68 bytes, image/png)",
+                "- `README.md`",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn text_structure_lists_every_directory_before_the_files() {
+        let files = [
+            "logo.png",
+            "README.md",
+            "src/app/mod.rs",
+            "src/main.rs",
+            "tests/it.rs",
+        ];
+        let lines = structure_lines(OutputFormat::Txt, &files);
+        assert_eq!(
+            lines,
+            vec![
+                "=== STRUCTURE ===",
+                ".",
+                "src",
+                "src/app",
+                "tests",
+                "logo.png [binary, 68 bytes, image/png]",
+                "README.md",
+                "src/app/mod.rs",
+                "src/main.rs",
+                "tests/it.rs",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn fence_is_longer_than_backtick_runs_in_content() {
+        assert_eq!(fence_for(0), "```");
+        assert_eq!(fence_for(3), "````");
+        assert_eq!(fence_for(5), "``````");
+    }
+
+    #[test]
+    fn language_tag_comes_from_extension_or_well_known_name() {
+        assert_eq!(language_for_path("src/lib.rs"), "rust");
+        assert_eq!(language_for_path("web/App.TSX"), "tsx");
+        assert_eq!(language_for_path("docker/Dockerfile"), "dockerfile");
+        assert_eq!(language_for_path("LICENSE"), "");
+    }
+
+    #[test]
+    fn inline_code_escapes_backticks_in_paths() {
+        assert_eq!(inline_code("a.rs"), "`a.rs`");
+        assert_eq!(inline_code("odd`name"), "`` odd`name ``");
+    }
+}
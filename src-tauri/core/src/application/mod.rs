@@ -0,0 +1,8 @@
+pub mod artifacts;
+pub mod budget;
+pub mod exporter;
+pub mod layout;
+pub mod progress;
+pub mod scanner;
+pub mod selection;
+pub mod session;
@@ -0,0 +1,25 @@
+use crate::models::ExportProgress;
+
+/// Watches a running export and can stop it; checked between files and while walking the tree.
+pub trait ExportObserver: Sync {
+    fn on_progress(&self, _progress: &ExportProgress) {}
+
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// For callers that neither report progress nor cancel.
+pub struct Unobserved;
+
+impl ExportObserver for Unobserved {}
+
+/// Passes cancellation through but not progress, for the measuring passes of a token budget
+/// whose file counts would otherwise rewind the reported progress.
+pub struct CancelOnly<'a>(pub &'a dyn ExportObserver);
+
+impl ExportObserver for CancelOnly<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
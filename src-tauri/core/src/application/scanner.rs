@@ -0,0 +1,375 @@
+use std::path::{Path, PathBuf};
+
+use crate::application::session::rule_engine_at;
+use crate::domain::rules::{Decision, RuleEngine};
+use crate::infrastructure::errors::{AppError, AppResult};
+use crate::infrastructure::fs_scan::scan_single_level;
+use crate::infrastructure::pathing::{
+    canonicalize_dir, display_path, ensure_under_root, file_name_or_fallback, relative_unix_path,
+};
+use crate::models::{DecisionExplanation, ExportConfig, ScanBatch, ScanLimits, ScanTree, TreeNode};
+
+pub fn scan_root(config: &ExportConfig, limits: &ScanLimits) -> AppResult<ScanTree> {
+    let root = canonicalize_dir(&config.root_path)?;
+    let engine = rule_engine_at(&root, config, &root)?;
+    let mut children = scan_single_level(&root, &root, limits, engine.gitignore())?;
+    apply_rule_decisions(&root, &engine, &mut children);
+    let mut warnings = engine.warnings().to_vec();
+    warnings.append(&mut children.warnings);
+    let root_node = TreeNode {
+        path: ".".to_string(),
+        name: file_name_or_fallback(&root, "workspace"),
+        is_dir: true,
+        children_count: Some(children.nodes.len()),
+        included_by_rules: true,
+        ignored_by_gitignore: false,
+        children: children.nodes,
+    };
+    Ok(ScanTree {
+        root: root_node,
+        warnings,
+    })
+}
+
+pub fn scan_children(
+    config: &ExportConfig,
+    dir_path: &str,
+    limits: &ScanLimits,
+) -> AppResult<ScanBatch> {
+    let root = canonicalize_dir(&config.root_path)?;
+    let dir_abs = resolve_dir_under_root(&root, dir_path)?;
+    let engine = rule_engine_at(&root, config, &dir_abs)?;
+
+    let mut warnings = engine.warnings().to_vec();
+
+    let depth = depth_from_root(&root, &dir_abs)?;
+    if depth >= limits.max_depth {
+        warnings.push(format!(
+            "Reached maxDepth limit ({}). Skipped deeper traversal.",
+            limits.max_depth
+        ));
+        return Ok(ScanBatch {
+            nodes: vec![],
+            warnings,
+        });
+    }
+
+    let mut batch = scan_single_level(&root, &dir_abs, limits, engine.gitignore())?;
+    apply_rule_decisions(&root, &engine, &mut batch);
+    warnings.append(&mut batch.warnings);
+    batch.warnings = warnings;
+    Ok(batch)
+}
+
+/// Explains the rule decision for one file or directory of the tree, given relative to the root.
+pub fn explain_path(config: &ExportConfig, path: &str) -> AppResult<DecisionExplanation> {
+    let root = canonicalize_dir(&config.root_path)?;
+    let abs_path = resolve_under_root(&root, path)?;
+    let engine = rule_engine_at(&root, config, &abs_path)?;
+    let rel_path = relative_unix_path(&root, &abs_path)?;
+    Ok(engine.explain(&rel_path, &abs_path, abs_path.is_dir()))
+}
+
+fn resolve_under_root(root: &Path, path: &str) -> AppResult<PathBuf> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() || trimmed == "." {
+        return Ok(root.to_path_buf());
+    }
+
+    let candidate = Path::new(trimmed);
+    if candidate.is_absolute() {
+        ensure_under_root(root, candidate)
+    } else {
+        ensure_under_root(root, &root.join(candidate))
+    }
+}
+
+fn resolve_dir_under_root(root: &Path, dir_path: &str) -> AppResult<PathBuf> {
+    let canonical = resolve_under_root(root, dir_path)?;
+    if !canonical.is_dir() {
+        return Err(AppError::DirPathNotDir {
+            path: display_path(&canonical),
+        });
+    }
+
+    Ok(canonical)
+}
+
+fn depth_from_root(root: &Path, target: &Path) -> AppResult<usize> {
+    let rel = target
+        .strip_prefix(root)
+        .map_err(|_| AppError::PathOutsideRoot {
+            path: display_path(target),
+        })?;
+    Ok(rel.components().count())
+}
+
+fn apply_rule_decisions(root: &Path, engine: &RuleEngine, batch: &mut ScanBatch) {
+    for node in &mut batch.nodes {
+        let abs_path = root.join(&node.path);
+        let decision = engine.should_include(&node.path, &abs_path, node.is_dir);
+        node.included_by_rules = matches!(decision, Decision::Include);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::application::selection::collect_selected_files;
+    use crate::infrastructure::errors::AppError;
+    use crate::models::{test_config, DecisionRule, ManualSelectionState, ScanLimits};
+
+    use super::{explain_path, scan_children, scan_root};
+
+    #[test]
+    fn scan_root_returns_first_level_and_keeps_directories_lazy() {
+        let root = tempdir().unwrap();
+        let nested_dir = root.path().join("a_dir");
+        fs::create_dir_all(&nested_dir).unwrap();
+        fs::write(nested_dir.join("deep.txt"), "nested").unwrap();
+        fs::write(root.path().join("root.txt"), "root").unwrap();
+
+        let config = test_config(root.path().to_string_lossy().as_ref());
+        let limits = ScanLimits::default();
+        let tree = scan_root(&config, &limits).unwrap().root;
+
+        assert_eq!(tree.path, ".");
+        assert_eq!(tree.children.len(), 2);
+        let dir_node = tree.children.iter().find(|node| node.is_dir).unwrap();
+        assert_eq!(dir_node.children_count, None);
+        assert!(dir_node.children.is_empty());
+    }
+
+    #[test]
+    fn scan_children_rejects_paths_outside_root() {
+        let root = tempdir().unwrap();
+        let outside = tempdir().unwrap();
+
+        let config = test_config(root.path().to_string_lossy().as_ref());
+        let limits = ScanLimits::default();
+        let result = scan_children(&config, outside.path().to_string_lossy().as_ref(), &limits);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::PathOutsideRoot { .. }
+        ));
+    }
+
+    #[test]
+    fn scan_children_rejects_file_path() {
+        let root = tempdir().unwrap();
+        let file_path = root.path().join("file.txt");
+        fs::write(&file_path, "content").unwrap();
+
+        let config = test_config(root.path().to_string_lossy().as_ref());
+        let limits = ScanLimits::default();
+        let result = scan_children(&config, "file.txt", &limits);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::DirPathNotDir { .. }
+        ));
+    }
+
+    #[test]
+    fn scan_root_marks_gitignored_entries_when_enabled() {
+        let root = tempdir().unwrap();
+        fs::write(
+            root.path().join(".gitignore"),
+            "ignored.txt\nignored_dir/\n",
+        )
+        .unwrap();
+        fs::write(root.path().join("ignored.txt"), "x").unwrap();
+        fs::write(root.path().join("normal.txt"), "y").unwrap();
+        fs::create_dir_all(root.path().join("ignored_dir")).unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = true;
+        let limits = ScanLimits::default();
+        let tree = scan_root(&config, &limits).unwrap().root;
+
+        let ignored_file = tree
+            .children
+            .iter()
+            .find(|node| node.path == "ignored.txt")
+            .unwrap();
+        let normal_file = tree
+            .children
+            .iter()
+            .find(|node| node.path == "normal.txt")
+            .unwrap();
+        let ignored_dir = tree
+            .children
+            .iter()
+            .find(|node| node.path == "ignored_dir")
+            .unwrap();
+
+        assert!(ignored_file.ignored_by_gitignore);
+        assert!(ignored_dir.ignored_by_gitignore);
+        assert!(!normal_file.ignored_by_gitignore);
+    }
+
+    #[test]
+    fn scan_root_hides_gitignored_entries_when_disabled() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        fs::write(root.path().join("ignored.txt"), "x").unwrap();
+        fs::write(root.path().join("normal.txt"), "y").unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = false;
+        let limits = ScanLimits::default();
+        let tree = scan_root(&config, &limits).unwrap().root;
+
+        let ignored_file = tree
+            .children
+            .iter()
+            .find(|node| node.path == "ignored.txt")
+            .unwrap();
+        let normal_file = tree
+            .children
+            .iter()
+            .find(|node| node.path == "normal.txt")
+            .unwrap();
+
+        assert!(!ignored_file.ignored_by_gitignore);
+        assert!(!normal_file.ignored_by_gitignore);
+    }
+
+    #[test]
+    fn scan_root_include_rules_override_exclude_and_gitignore() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join(".gitignore"), "kept.ts\n").unwrap();
+        fs::write(root.path().join("kept.ts"), "x").unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = true;
+        config.include_extensions = vec![".ts".to_string()];
+        config.exclude_extensions = vec![".ts".to_string()];
+        let limits = ScanLimits::default();
+        let tree = scan_root(&config, &limits).unwrap().root;
+
+        let node = tree
+            .children
+            .iter()
+            .find(|item| item.path == "kept.ts")
+            .unwrap();
+        assert!(node.ignored_by_gitignore);
+        assert!(node.included_by_rules);
+    }
+
+    #[test]
+    fn scan_root_manual_selection_still_has_highest_priority() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join("file.ts"), "x").unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.include_extensions = vec![".ts".to_string()];
+        config.exclude_extensions = vec![".ts".to_string()];
+        config
+            .manual_selections
+            .insert("file.ts".to_string(), ManualSelectionState::Exclude);
+        let limits = ScanLimits::default();
+        let tree = scan_root(&config, &limits).unwrap().root;
+
+        let node = tree
+            .children
+            .iter()
+            .find(|item| item.path == "file.ts")
+            .unwrap();
+        assert!(!node.included_by_rules);
+    }
+
+    #[test]
+    fn scan_responses_report_ignore_file_warnings() {
+        let root = tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src")).unwrap();
+        fs::write(root.path().join(".gitignore"), "*.log\n[z-a]\n").unwrap();
+        fs::write(root.path().join("src").join("main.rs"), "x").unwrap();
+
+        let config = test_config(root.path().to_string_lossy().as_ref());
+        let limits = ScanLimits::default();
+        let tree = scan_root(&config, &limits).unwrap();
+        let batch = scan_children(&config, "src", &limits).unwrap();
+        let selection = collect_selected_files(&config, &limits).unwrap();
+
+        assert_eq!(tree.warnings.len(), 1);
+        assert!(tree.warnings[0].contains("[z-a]"));
+        assert_eq!(batch.warnings, tree.warnings);
+        assert_eq!(selection.warnings, tree.warnings);
+    }
+
+    #[test]
+    fn explain_path_reports_gitignore_file_and_line() {
+        let root = tempdir().unwrap();
+        fs::create_dir_all(root.path().join("logs")).unwrap();
+        fs::write(
+            root.path().join(".gitignore"),
+            "# build output\n*.tmp\nlogs/\n",
+        )
+        .unwrap();
+        fs::write(root.path().join("logs").join("app.log"), "x").unwrap();
+
+        let config = test_config(root.path().to_string_lossy().as_ref());
+        let explanation = explain_path(&config, "logs/app.log").unwrap();
+
+        assert!(!explanation.included);
+        assert_eq!(explanation.rule, DecisionRule::Gitignore);
+        assert_eq!(explanation.pattern.as_deref(), Some("logs/"));
+        let source = explanation.source.unwrap();
+        assert_eq!(source.file, ".gitignore");
+        assert_eq!(source.line, Some(3));
+        assert!(explanation.summary.contains(".gitignore:3"));
+    }
+
+    #[test]
+    fn explain_path_names_the_deciding_rule_and_pattern() {
+        let root = tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src").join("gen")).unwrap();
+        fs::write(root.path().join("src").join("gen").join("api.rs"), "x").unwrap();
+        fs::write(root.path().join("src").join("main.rs"), "x").unwrap();
+        fs::write(root.path().join("notes.md"), "x").unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.exclude_globs = vec!["dist/**".to_string(), "src/gen/**".to_string()];
+        config.exclude_extensions = vec!["md".to_string()];
+        config
+            .manual_selections
+            .insert("src/gen".to_string(), ManualSelectionState::Inherit);
+
+        let generated = explain_path(&config, "src/gen/api.rs").unwrap();
+        assert_eq!(generated.rule, DecisionRule::ExcludeGlob);
+        assert_eq!(generated.pattern.as_deref(), Some("src/gen/**"));
+
+        let notes = explain_path(&config, "notes.md").unwrap();
+        assert_eq!(notes.rule, DecisionRule::ExcludeExtension);
+        assert_eq!(notes.pattern.as_deref(), Some(".md"));
+
+        config
+            .manual_selections
+            .insert("src".to_string(), ManualSelectionState::Exclude);
+        let main = explain_path(&config, "src/main.rs").unwrap();
+        assert_eq!(main.rule, DecisionRule::ManualExclude);
+        assert_eq!(main.pattern.as_deref(), Some("src"));
+
+        fs::create_dir_all(root.path().join("node_modules").join("react")).unwrap();
+        let dependency = explain_path(&config, "node_modules/react").unwrap();
+        assert_eq!(dependency.rule, DecisionRule::HardExcludeList);
+        assert_eq!(dependency.pattern.as_deref(), Some("node_modules"));
+
+        fs::write(root.path().join(".env.local"), "TOKEN=1").unwrap();
+        let env = explain_path(&config, ".env.local").unwrap();
+        assert!(!env.included);
+        assert_eq!(env.rule, DecisionRule::SensitiveFile);
+        assert_eq!(env.pattern.as_deref(), Some(".env.*"));
+
+        let git_dir = root.path().join(".git");
+        fs::create_dir_all(&git_dir).unwrap();
+        let git = explain_path(&config, ".git").unwrap();
+        assert!(git.is_dir);
+        assert_eq!(git.rule, DecisionRule::HardExclude);
+    }
+}
@@ -0,0 +1,527 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::{fs, path::Path};
+
+use walkdir::WalkDir;
+
+use crate::application::artifacts::ArtifactFilter;
+use crate::application::progress::{ExportObserver, Unobserved};
+use crate::application::session::rule_engine;
+use crate::domain::rules::{Decision, RuleEngine};
+use crate::infrastructure::errors::{AppError, AppResult};
+use crate::infrastructure::pathing::{canonicalize_dir, relative_unix_path};
+use crate::infrastructure::sorting::compare_path_order;
+use crate::models::{ExportConfig, ExportPhase, ExportProgress, ScanLimits};
+
+/// Walked entries between cancellation checks and progress reports.
+const PROGRESS_INTERVAL: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct SelectedFile {
+    pub abs_path: PathBuf,
+    pub rel_path: String,
+    pub size: u64,
+    /// Upper bound on exported content bytes, set when a token budget truncates the file.
+    pub byte_limit: Option<u64>,
+    /// On the sensitive file list; selected only through a manual include of the file itself.
+    pub sensitive: bool,
+    /// Index of the `ExportConfig::size_overrides` entry in force for the file.
+    pub size_override: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectionRun {
+    pub files: Vec<SelectedFile>,
+    pub included_files: usize,
+    pub excluded_files: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Walks the root and yields the selected files one at a time, in output order: relative paths
+/// compared case-insensitively, files and directories interleaved. Only the directories on the
+/// current path are held in memory, so the walk itself stays small however large the tree is;
+/// an export still keeps one structure entry per file it yields (see `StructureLines`).
+///
+/// The counts and warnings cover the entries walked so far and are final once the iterator is
+/// exhausted.
+pub struct SelectionWalk<'a> {
+    root: PathBuf,
+    engine: Arc<RuleEngine>,
+    entries: walkdir::IntoIter,
+    limits: ScanLimits,
+    observer: &'a dyn ExportObserver,
+    reports_progress: bool,
+    artifacts: Option<ArtifactFilter>,
+    included: usize,
+    excluded: usize,
+    warnings: Vec<String>,
+    walked: usize,
+    depth_warning_emitted: bool,
+    done: bool,
+}
+
+impl<'a> SelectionWalk<'a> {
+    pub fn new(
+        config: &ExportConfig,
+        limits: &ScanLimits,
+        observer: &'a dyn ExportObserver,
+    ) -> AppResult<Self> {
+        let root = canonicalize_dir(&config.root_path)?;
+        let engine = rule_engine(&root, config)?;
+        let warnings = engine.warnings().to_vec();
+        let entries = WalkDir::new(&root)
+            .follow_links(false)
+            .max_depth(limits.max_depth)
+            .sort_by(|a, b| {
+                compare_path_order(
+                    a.file_name(),
+                    a.file_type().is_dir(),
+                    b.file_name(),
+                    b.file_type().is_dir(),
+                )
+            })
+            .into_iter();
+
+        Ok(Self {
+            root,
+            engine,
+            entries,
+            limits: limits.clone(),
+            observer,
+            reports_progress: true,
+            artifacts: None,
+            included: 0,
+            excluded: 0,
+            warnings,
+            walked: 0,
+            depth_warning_emitted: false,
+            done: false,
+        })
+    }
+
+    /// Also drops files an export produced, each reported in the warnings.
+    pub fn excluding_export_artifacts(mut self, output_path: Option<&Path>) -> Self {
+        self.artifacts = Some(ArtifactFilter::new(output_path));
+        self
+    }
+
+    /// Still checks for cancellation, but leaves progress to whoever consumes the walk.
+    pub fn without_progress(mut self) -> Self {
+        self.reports_progress = false;
+        self
+    }
+
+    pub fn included_files(&self) -> usize {
+        self.included
+    }
+
+    pub fn excluded_files(&self) -> usize {
+        self.excluded
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn into_warnings(self) -> Vec<String> {
+        self.warnings
+    }
+
+    fn next_selected(&mut self) -> AppResult<Option<SelectedFile>> {
+        while !self.done {
+            let Some(entry) = self.entries.next() else {
+                break;
+            };
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            if path == self.root {
+                continue;
+            }
+
+            let rel_path = relative_unix_path(&self.root, path)?;
+            if self.walked.is_multiple_of(PROGRESS_INTERVAL) {
+                if self.observer.is_cancelled() {
+                    return Err(AppError::ExportCancelled);
+                }
+                if self.reports_progress {
+                    self.observer.on_progress(&ExportProgress {
+                        phase: ExportPhase::Selecting,
+                        files_processed: self.included + self.excluded,
+                        total_files: 0,
+                        selection_complete: false,
+                        bytes_written: 0,
+                        current_path: Some(rel_path.clone()),
+                    });
+                }
+            }
+            self.walked += 1;
+            let is_dir = entry.file_type().is_dir();
+
+            // Excluded directories are skipped whole, so they neither cost a walk nor count
+            // towards maxFiles.
+            if is_dir && self.engine.prunes_dir(&rel_path, path) {
+                self.entries.skip_current_dir();
+                continue;
+            }
+            if is_dir
+                && entry.depth() >= self.limits.max_depth
+                && !self.depth_warning_emitted
+                && dir_has_descendants(path)
+            {
+                self.warnings.push(format!(
+                    "Reached maxDepth limit ({}). Skipped deeper traversal.",
+                    self.limits.max_depth
+                ));
+                self.depth_warning_emitted = true;
+            }
+            if is_dir {
+                continue;
+            }
+
+            let selected = match self.engine.should_include(&rel_path, path, is_dir) {
+                Decision::Include => {
+                    let file = SelectedFile {
+                        abs_path: path.to_path_buf(),
+                        size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                        byte_limit: None,
+                        sensitive: self.engine.is_sensitive(&rel_path),
+                        size_override: self.engine.size_override(&rel_path),
+                        rel_path,
+                    };
+                    match self
+                        .artifacts
+                        .as_ref()
+                        .and_then(|filter| filter.exclusion(&file))
+                    {
+                        Some(warning) => {
+                            self.warnings.push(warning);
+                            self.excluded += 1;
+                            None
+                        }
+                        None => {
+                            self.included += 1;
+                            Some(file)
+                        }
+                    }
+                }
+                Decision::Exclude => {
+                    self.excluded += 1;
+                    None
+                }
+            };
+
+            if self.included + self.excluded >= self.limits.max_files {
+                self.warnings.push(format!(
+                    "Reached maxFiles limit ({}). Remaining files were skipped.",
+                    self.limits.max_files
+                ));
+                self.done = true;
+            }
+            if selected.is_some() {
+                return Ok(selected);
+            }
+        }
+        self.done = true;
+        Ok(None)
+    }
+}
+
+impl Iterator for SelectionWalk<'_> {
+    type Item = AppResult<SelectedFile>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next_selected();
+        if next.is_err() {
+            self.done = true;
+        }
+        next.transpose()
+    }
+}
+
+pub fn collect_selected_files(
+    config: &ExportConfig,
+    limits: &ScanLimits,
+) -> AppResult<SelectionRun> {
+    collect_selected_files_observed(config, limits, &Unobserved)
+}
+
+pub fn collect_selected_files_observed(
+    config: &ExportConfig,
+    limits: &ScanLimits,
+    observer: &dyn ExportObserver,
+) -> AppResult<SelectionRun> {
+    collect_walk(SelectionWalk::new(config, limits, observer)?)
+}
+
+/// Sensitive files the rules select, found without walking: a sensitive file is selected only
+/// through a manual include of the file itself. Files a limit would cut are listed too.
+pub fn sensitive_selections(config: &ExportConfig) -> AppResult<Vec<String>> {
+    let root = canonicalize_dir(&config.root_path)?;
+    let engine = rule_engine(&root, config)?;
+    let paths = engine
+        .sensitive_manual_includes()
+        .filter(|rel_path| {
+            let abs_path = root.join(rel_path);
+            fs::symlink_metadata(&abs_path).is_ok_and(|metadata| !metadata.is_dir())
+                && engine.should_include(rel_path, &abs_path, false) == Decision::Include
+        })
+        .map(str::to_string)
+        .collect();
+    Ok(paths)
+}
+
+/// Holds the whole selection in memory, for callers that need every file at once.
+pub fn collect_walk(mut walk: SelectionWalk<'_>) -> AppResult<SelectionRun> {
+    let files = walk.by_ref().collect::<AppResult<Vec<_>>>()?;
+    Ok(SelectionRun {
+        files,
+        included_files: walk.included_files(),
+        excluded_files: walk.excluded_files(),
+        warnings: walk.into_warnings(),
+    })
+}
+
+fn dir_has_descendants(path: &Path) -> bool {
+    fs::read_dir(path)
+        .ok()
+        .and_then(|mut iter| iter.next())
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::models::{test_config, ManualSelectionState, ScanLimits};
+
+    use crate::application::progress::Unobserved;
+
+    use super::{collect_selected_files, SelectionWalk};
+
+    #[test]
+    fn manual_include_overrides_gitignore_but_not_hard_exclude() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        fs::write(root.path().join("ignored.txt"), "hello").unwrap();
+        fs::create_dir_all(root.path().join(".git")).unwrap();
+        fs::write(root.path().join(".git").join("config"), "internal").unwrap();
+
+        let mut manual = BTreeMap::new();
+        manual.insert("ignored.txt".to_string(), ManualSelectionState::Include);
+        manual.insert(".git/config".to_string(), ManualSelectionState::Include);
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.manual_selections = manual;
+
+        let run = collect_selected_files(&config, &ScanLimits::default()).unwrap();
+        assert!(run.files.iter().any(|item| item.rel_path == "ignored.txt"));
+        assert!(!run
+            .files
+            .iter()
+            .any(|item| item.rel_path.starts_with(".git/")));
+    }
+
+    #[test]
+    fn hard_exclude_list_yields_only_to_manual_include_at_or_below_it() {
+        let root = tempdir().unwrap();
+        for dir in ["node_modules/left-pad", "packages/app/dist", "src"] {
+            fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        fs::write(root.path().join("node_modules/left-pad/index.js"), "x").unwrap();
+        fs::write(root.path().join("packages/app/dist/app.js"), "x").unwrap();
+        fs::write(root.path().join("packages/app/yarn.lock"), "x").unwrap();
+        fs::write(root.path().join("src/main.rs"), "x").unwrap();
+
+        let mut manual = BTreeMap::new();
+        manual.insert(
+            "node_modules/left-pad".to_string(),
+            ManualSelectionState::Include,
+        );
+        manual.insert("packages".to_string(), ManualSelectionState::Include);
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = false;
+        config.manual_selections = manual;
+
+        let run = collect_selected_files(&config, &ScanLimits::default()).unwrap();
+        let included: Vec<&str> = run
+            .files
+            .iter()
+            .map(|item| item.rel_path.as_str())
+            .collect();
+        assert_eq!(
+            included,
+            vec!["node_modules/left-pad/index.js", "src/main.rs"]
+        );
+
+        config.hard_excludes = vec!["*.lock".to_string()];
+        let run = collect_selected_files(&config, &ScanLimits::default()).unwrap();
+        let included: Vec<&str> = run
+            .files
+            .iter()
+            .map(|item| item.rel_path.as_str())
+            .collect();
+        assert_eq!(
+            included,
+            vec![
+                "node_modules/left-pad/index.js",
+                "packages/app/dist/app.js",
+                "src/main.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn include_rules_override_exclude_and_gitignore_when_manual_is_inherit() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        fs::write(root.path().join("ignored.txt"), "ignored").unwrap();
+        fs::write(root.path().join("blocked.txt"), "blocked").unwrap();
+        fs::write(root.path().join("allowed.txt"), "allowed").unwrap();
+
+        let mut manual = BTreeMap::new();
+        manual.insert("ignored.txt".to_string(), ManualSelectionState::Inherit);
+        manual.insert("blocked.txt".to_string(), ManualSelectionState::Inherit);
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.include_globs = vec!["*.txt".to_string()];
+        config.exclude_globs = vec!["blocked.txt".to_string()];
+        config.manual_selections = manual;
+
+        let run = collect_selected_files(&config, &ScanLimits::default()).unwrap();
+        let included: Vec<&str> = run
+            .files
+            .iter()
+            .map(|item| item.rel_path.as_str())
+            .collect();
+        assert_eq!(included, vec!["allowed.txt", "blocked.txt", "ignored.txt"]);
+        assert_eq!(run.excluded_files, 1);
+    }
+
+    #[test]
+    fn emits_max_files_warning_when_limit_is_hit() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join("a.txt"), "a").unwrap();
+        fs::write(root.path().join("b.txt"), "b").unwrap();
+        fs::write(root.path().join("c.txt"), "c").unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = false;
+        let limits = ScanLimits {
+            max_files: 2,
+            max_depth: 64,
+        };
+
+        let run = collect_selected_files(&config, &limits).unwrap();
+        assert_eq!(run.included_files + run.excluded_files, 2);
+        assert!(run
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("Reached maxFiles limit")));
+    }
+
+    #[test]
+    fn excluded_directories_do_not_count_towards_max_files() {
+        let root = tempdir().unwrap();
+        for dir in ["node_modules/left-pad", "generated", "src"] {
+            fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        for index in 0..50 {
+            fs::write(
+                root.path()
+                    .join(format!("node_modules/left-pad/{index}.js")),
+                "x",
+            )
+            .unwrap();
+            fs::write(root.path().join(format!("generated/{index}.rs")), "x").unwrap();
+        }
+        fs::write(root.path().join(".gitignore"), "generated/\n").unwrap();
+        fs::write(root.path().join("src/main.rs"), "x").unwrap();
+
+        let config = test_config(root.path().to_string_lossy().as_ref());
+        let limits = ScanLimits {
+            max_files: 3,
+            max_depth: 64,
+        };
+
+        let run = collect_selected_files(&config, &limits).unwrap();
+        let included: Vec<&str> = run
+            .files
+            .iter()
+            .map(|item| item.rel_path.as_str())
+            .collect();
+        assert_eq!(included, vec![".gitignore", "src/main.rs"]);
+        assert_eq!(run.excluded_files, 0);
+        assert!(run.warnings.is_empty());
+    }
+
+    #[test]
+    fn emits_max_depth_warning_when_deeper_directories_exist() {
+        let root = tempdir().unwrap();
+        let level1 = root.path().join("level1");
+        let level2 = level1.join("level2");
+        fs::create_dir_all(&level2).unwrap();
+        fs::write(level2.join("deep.txt"), "deep").unwrap();
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = false;
+        let limits = ScanLimits {
+            max_files: 100_000,
+            max_depth: 1,
+        };
+
+        let run = collect_selected_files(&config, &limits).unwrap();
+        assert_eq!(run.included_files, 0);
+        assert!(run
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("Reached maxDepth limit")));
+    }
+
+    #[test]
+    fn walk_yields_files_in_case_insensitive_path_order() {
+        let root = tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src/app")).unwrap();
+        fs::create_dir_all(root.path().join("src-gen")).unwrap();
+        for file in [
+            "src/app/mod.rs",
+            "src/main.rs",
+            "src-gen/api.rs",
+            "src.rs",
+            "README.md",
+            "b.txt",
+            "A.txt",
+            "skip.log",
+        ] {
+            fs::write(root.path().join(file), "x").unwrap();
+        }
+
+        let mut config = test_config(root.path().to_string_lossy().as_ref());
+        config.use_gitignore = false;
+        config.exclude_extensions = vec!["log".to_string()];
+
+        let mut walk = SelectionWalk::new(&config, &ScanLimits::default(), &Unobserved).unwrap();
+        let first = walk.next().unwrap().unwrap();
+        assert_eq!(first.rel_path, "A.txt");
+        assert_eq!(walk.included_files(), 1);
+
+        let rest: Vec<String> = walk.by_ref().map(|file| file.unwrap().rel_path).collect();
+        assert_eq!(
+            rest,
+            vec![
+                "b.txt",
+                "README.md",
+                "src-gen/api.rs",
+                "src.rs",
+                "src/app/mod.rs",
+                "src/main.rs"
+            ]
+        );
+        assert_eq!((walk.included_files(), walk.excluded_files()), (7, 1));
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use crate::domain::rules::{HardExcludes, RuleEngine};
+use crate::infrastructure::errors::AppResult;
+use crate::infrastructure::gitignore::GitignoreSet;
+use crate::models::ExportConfig;
+
+const MAX_CACHED_ROOTS: usize = 8;
+const MAX_ENGINES_PER_ROOT: usize = 4;
+
+static SESSIONS: OnceLock<Mutex<HashMap<PathBuf, RootSession>>> = OnceLock::new();
+
+/// Compiled rule state for one root, reused across scan, explain and export calls until the
+/// rules change or an ignore file under the root is edited, added or removed.
+#[derive(Default)]
+struct RootSession {
+    /// Keyed by `ignore_hash`, since the loader skips directories the config excludes.
+    gitignore: Option<(u64, Arc<GitignoreSet>)>,
+    /// Most recently used first, keyed by `rules_hash`.
+    engines: Vec<(u64, Arc<RuleEngine>)>,
+    last_used: u64,
+}
+
+/// Returns the rule engine for `root` under `config`, building it only when no cached engine
+/// matches the rules or the cached ignore set is stale. `root` must already be canonical.
+///
+/// Checking staleness re-stats every directory under the root, which walks such as an export
+/// need anyway; see `rule_engine_at` for calls that only look at one path.
+pub fn rule_engine(root: &Path, config: &ExportConfig) -> AppResult<Arc<RuleEngine>> {
+    cached_rule_engine(root, config, None)
+}
+
+/// `rule_engine` for decisions at and directly below `path`, such as listing one directory of
+/// the tree: only the ignore state that can affect them is checked, so the cost follows the
+/// depth of `path` rather than the size of the tree.
+pub fn rule_engine_at(
+    root: &Path,
+    config: &ExportConfig,
+    path: &Path,
+) -> AppResult<Arc<RuleEngine>> {
+    cached_rule_engine(root, config, Some(path))
+}
+
+fn cached_rule_engine(
+    root: &Path,
+    config: &ExportConfig,
+    scope: Option<&Path>,
+) -> AppResult<Arc<RuleEngine>> {
+    let key = rules_hash(config);
+    let ignore_key = ignore_hash(config);
+    let (cached_gitignore, cached_engine) = {
+        let mut sessions = lock_sessions();
+        let tick = next_tick(&sessions);
+        let session = sessions.entry(root.to_path_buf()).or_default();
+        session.last_used = tick;
+        if session
+            .gitignore
+            .as_ref()
+            .is_some_and(|(_, set)| match scope {
+                Some(path) => !set.is_current_at(path),
+                None => !set.is_current(),
+            })
+        {
+            *session = RootSession {
+                last_used: tick,
+                ..RootSession::default()
+            };
+        }
+        let engine = session
+            .engines
+            .iter()
+            .position(|(hash, _)| *hash == key)
+            .map(|index| {
+                let entry = session.engines.remove(index);
+                let engine = Arc::clone(&entry.1);
+                session.engines.insert(0, entry);
+                engine
+            });
+        let gitignore = session
+            .gitignore
+            .as_ref()
+            .filter(|(hash, _)| *hash == ignore_key)
+            .map(|(_, set)| Arc::clone(set));
+        (gitignore, engine)
+    };
+    if let Some(engine) = cached_engine {
+        return Ok(engine);
+    }
+
+    let gitignore = match cached_gitignore {
+        Some(gitignore) => gitignore,
+        None => {
+            let hard_excludes = HardExcludes::from_config(config)?;
+            Arc::new(GitignoreSet::load_pruned(
+                root,
+                config.use_gitignore,
+                config.ignore_sources,
+                |rel_path| hard_excludes.prunes_dir(rel_path),
+            ))
+        }
+    };
+    let engine = Arc::new(RuleEngine::with_gitignore(
+        root,
+        config,
+        Arc::clone(&gitignore),
+    )?);
+
+    let mut sessions = lock_sessions();
+    let tick = next_tick(&sessions);
+    let session = sessions.entry(root.to_path_buf()).or_default();
+    session.last_used = tick;
+    session.gitignore = Some((ignore_key, gitignore));
+    session.engines.insert(0, (key, Arc::clone(&engine)));
+    session.engines.truncate(MAX_ENGINES_PER_ROOT);
+    evict_least_recent(&mut sessions);
+    Ok(engine)
+}
+
+fn lock_sessions() -> std::sync::MutexGuard<'static, HashMap<PathBuf, RootSession>> {
+    SESSIONS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+fn next_tick(sessions: &HashMap<PathBuf, RootSession>) -> u64 {
+    sessions.values().map(|s| s.last_used).max().unwrap_or(0) + 1
+}
+
+fn evict_least_recent(sessions: &mut HashMap<PathBuf, RootSession>) {
+    while sessions.len() > MAX_CACHED_ROOTS {
+        let Some(oldest) = sessions
+            .iter()
+            .min_by_key(|(_, session)| session.last_used)
+            .map(|(root, _)| root.clone())
+        else {
+            break;
+        };
+        sessions.remove(&oldest);
+    }
+}
+
+/// Hashes the config fields `RuleEngine` is built from; output and budget settings don't
+/// affect rule decisions and would only fragment the cache.
+fn rules_hash(config: &ExportConfig) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    config.use_gitignore.hash(&mut hasher);
+    config.ignore_sources.hash(&mut hasher);
+    config.include_globs.hash(&mut hasher);
+    config.exclude_globs.hash(&mut hasher);
+    config.include_extensions.hash(&mut hasher);
+    config.exclude_extensions.hash(&mut hasher);
+    config.manual_selections.hash(&mut hasher);
+    config.hard_excludes.hash(&mut hasher);
+    for entry in &config.size_overrides {
+        entry.glob.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Hashes the config fields `GitignoreSet::load_pruned` depends on.
+fn ignore_hash(config: &ExportConfig) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    config.use_gitignore.hash(&mut hasher);
+    config.ignore_sources.hash(&mut hasher);
+    config.hard_excludes.hash(&mut hasher);
+    config.manual_selections.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+
+    use tempfile::tempdir;
+
+    use crate::models::{test_config, SizeOverride};
+
+    use super::{rule_engine, rule_engine_at};
+
+    #[test]
+    fn reuses_engine_until_rules_change() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        fs::write(root_path.join(".gitignore"), "*.log\n").unwrap();
+        let mut config = test_config(root_path.to_string_lossy().as_ref());
+
+        let first = rule_engine(&root_path, &config).unwrap();
+        let second = rule_engine(&root_path, &config).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        config.max_file_size_kb = 1;
+        assert!(Arc::ptr_eq(
+            &first,
+            &rule_engine(&root_path, &config).unwrap()
+        ));
+
+        config.exclude_globs = vec!["*.md".to_string()];
+        let changed = rule_engine(&root_path, &config).unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+
+        config.size_overrides = vec![SizeOverride {
+            glob: "*.json".to_string(),
+            max_file_size_kb: Some(20),
+            max_file_lines: None,
+            large_file_strategy: None,
+        }];
+        let with_override = rule_engine(&root_path, &config).unwrap();
+        assert!(!Arc::ptr_eq(&changed, &with_override));
+        assert_eq!(with_override.size_override("fixtures/a.json"), Some(0));
+    }
+
+    #[test]
+    fn rebuilds_after_gitignore_edits() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root_path.join("src")).unwrap();
+        fs::write(root_path.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root_path.join("src/app.tmp"), "").unwrap();
+        let config = test_config(root_path.to_string_lossy().as_ref());
+        let matched = |engine: &super::RuleEngine, rel: &str| {
+            engine
+                .gitignore()
+                .matched(&root_path.join(rel), false)
+                .is_ignore()
+        };
+
+        let first = rule_engine(&root_path, &config).unwrap();
+        assert!(matched(&first, "debug.log"));
+
+        fs::write(root_path.join(".gitignore"), "*.trace\n").unwrap();
+        let edited = rule_engine(&root_path, &config).unwrap();
+        assert!(!Arc::ptr_eq(&first, &edited));
+        assert!(!matched(&edited, "debug.log"));
+
+        fs::write(root_path.join("src/.gitignore"), "*.tmp\n").unwrap();
+        let nested = rule_engine(&root_path, &config).unwrap();
+        assert!(!Arc::ptr_eq(&edited, &nested));
+        assert!(matched(&nested, "src/app.tmp"));
+    }
+
+    #[test]
+    fn path_lookups_only_check_ignore_state_on_their_path() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root_path.join("src")).unwrap();
+        fs::create_dir_all(root_path.join("docs")).unwrap();
+        fs::write(root_path.join(".gitignore"), "*.log\n").unwrap();
+        let config = test_config(root_path.to_string_lossy().as_ref());
+        let src = root_path.join("src");
+
+        let first = rule_engine(&root_path, &config).unwrap();
+        fs::write(root_path.join("docs/.gitignore"), "*.md\n").unwrap();
+        let unrelated = rule_engine_at(&root_path, &config, &src).unwrap();
+        assert!(Arc::ptr_eq(&first, &unrelated));
+
+        fs::write(root_path.join("src/.gitignore"), "*.tmp\n").unwrap();
+        let rebuilt = rule_engine_at(&root_path, &config, &src).unwrap();
+        assert!(!Arc::ptr_eq(&first, &rebuilt));
+        let ignored = |rel: &str| {
+            rebuilt
+                .gitignore()
+                .matched(&root_path.join(rel), false)
+                .is_ignore()
+        };
+        assert!(ignored("src/app.tmp"));
+        assert!(ignored("docs/guide.md"));
+    }
+}
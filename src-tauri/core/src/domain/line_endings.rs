@@ -0,0 +1,131 @@
+use crate::models::{FileLineStats, LineEndingStyle};
+
+/// Counts line endings and lines with trailing spaces or tabs over text fed in chunks; a CRLF
+/// split between two chunks still counts once.
+#[derive(Debug, Default)]
+pub struct LineStats {
+    lf: usize,
+    crlf: usize,
+    cr: usize,
+    trailing_whitespace: usize,
+    pending_cr: bool,
+    line_ends_blank: bool,
+}
+
+impl LineStats {
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if self.pending_cr {
+                self.pending_cr = false;
+                if byte == b'\n' {
+                    self.crlf += 1;
+                    continue;
+                }
+                self.cr += 1;
+            }
+            match byte {
+                b'\r' => {
+                    self.end_line();
+                    self.pending_cr = true;
+                }
+                b'\n' => {
+                    self.end_line();
+                    self.lf += 1;
+                }
+                b' ' | b'\t' => self.line_ends_blank = true,
+                _ => self.line_ends_blank = false,
+            }
+        }
+    }
+
+    /// Closes a trailing CR and the last, unterminated line.
+    pub fn finish(&mut self) {
+        if self.pending_cr {
+            self.pending_cr = false;
+            self.cr += 1;
+        }
+        self.end_line();
+    }
+
+    pub fn style(&self) -> Option<LineEndingStyle> {
+        match (self.lf > 0, self.crlf > 0, self.cr > 0) {
+            (false, false, false) => None,
+            (true, false, false) => Some(LineEndingStyle::Lf),
+            (false, true, false) => Some(LineEndingStyle::Crlf),
+            (false, false, true) => Some(LineEndingStyle::Cr),
+            _ => Some(LineEndingStyle::Mixed),
+        }
+    }
+
+    /// Anything besides LF line endings.
+    pub fn needs_normalizing(&self) -> bool {
+        self.crlf > 0 || self.cr > 0
+    }
+
+    /// "CRLF", or the counts of each ending when they are mixed.
+    pub fn describe(&self) -> String {
+        match self.style() {
+            Some(LineEndingStyle::Mixed) => {
+                format!("mixed ({} CRLF, {} LF, {} CR)", self.crlf, self.lf, self.cr)
+            }
+            Some(LineEndingStyle::Crlf) => "CRLF".to_string(),
+            Some(LineEndingStyle::Cr) => "CR".to_string(),
+            Some(LineEndingStyle::Lf) => "LF".to_string(),
+            None => "none".to_string(),
+        }
+    }
+
+    /// `None` for files with only LF endings and no trailing whitespace.
+    pub fn notable(&self, path: &str) -> Option<FileLineStats> {
+        if !self.needs_normalizing() && self.trailing_whitespace == 0 {
+            return None;
+        }
+        Some(FileLineStats {
+            path: path.to_string(),
+            line_endings: self.style(),
+            lf_lines: self.lf,
+            crlf_lines: self.crlf,
+            cr_lines: self.cr,
+            trailing_whitespace_lines: self.trailing_whitespace,
+        })
+    }
+
+    fn end_line(&mut self) {
+        if self.line_ends_blank {
+            self.trailing_whitespace += 1;
+        }
+        self.line_ends_blank = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::LineEndingStyle;
+
+    use super::LineStats;
+
+    fn stats_of(chunks: &[&[u8]]) -> LineStats {
+        let mut stats = LineStats::default();
+        for chunk in chunks {
+            stats.feed(chunk);
+        }
+        stats.finish();
+        stats
+    }
+
+    #[test]
+    fn counts_each_ending_across_chunk_boundaries() {
+        let stats = stats_of(&[b"a\r", b"\nb \nc\r", b"d\t"]);
+        assert_eq!(stats.style(), Some(LineEndingStyle::Mixed));
+        assert_eq!(stats.describe(), "mixed (1 CRLF, 1 LF, 1 CR)");
+
+        let file = stats.notable("mixed.txt").unwrap();
+        assert_eq!((file.crlf_lines, file.lf_lines, file.cr_lines), (1, 1, 1));
+        assert_eq!(file.trailing_whitespace_lines, 2);
+
+        assert_eq!(stats_of(&[b"@echo off\r\n"]).describe(), "CRLF");
+        assert_eq!(stats_of(&[b"x\r"]).style(), Some(LineEndingStyle::Cr));
+        assert!(stats_of(&[b"fn main() {}\n"]).notable("main.rs").is_none());
+        assert_eq!(stats_of(&[b"no newline"]).style(), None);
+    }
+}
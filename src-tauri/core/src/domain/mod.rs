@@ -0,0 +1,3 @@
+pub mod line_endings;
+pub mod rules;
+pub mod secrets;
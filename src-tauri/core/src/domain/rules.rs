@@ -0,0 +1,641 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::Glob as GitignoreGlob;
+use ignore::Match;
+
+use crate::infrastructure::errors::{AppError, AppResult};
+use crate::infrastructure::gitignore::GitignoreSet;
+use crate::models::{
+    DecisionExplanation, DecisionRule, ExportConfig, ManualSelectionState, RuleSource,
+};
+
+/// File names that usually hold credentials. Matched against the file name only; a match is
+/// excluded unless the file itself is manually included, and exporting it needs confirmation.
+const SENSITIVE_FILE_PATTERNS: &[&str] = &[
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    "*.p12",
+    "*.pfx",
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+    "credentials.json",
+    ".npmrc",
+    ".pypirc",
+    ".netrc",
+];
+
+static SENSITIVE_FILES: OnceLock<GlobSet> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Include,
+    Exclude,
+}
+
+pub struct RuleEngine {
+    root: PathBuf,
+    include_globs: Option<GlobSet>,
+    exclude_globs: Option<GlobSet>,
+    include_patterns: Vec<String>,
+    exclude_patterns: Vec<String>,
+    include_ext: HashSet<String>,
+    exclude_ext: HashSet<String>,
+    hard_excludes: HardExcludes,
+    manual: BTreeMap<String, ManualSelectionState>,
+    gitignore: Arc<GitignoreSet>,
+    size_overrides: Option<GlobSet>,
+}
+
+/// Outcome of the rule chain before it is reduced to a `Decision` or explained.
+enum Verdict<'a> {
+    HardExcluded,
+    HardExcludeList(&'a str),
+    SensitiveFile(&'static str),
+    Manual { key: &'a str, include: bool },
+    IncludeGlobMismatch,
+    IncludeExtensionMismatch,
+    IncludeGlob,
+    IncludeExtension,
+    ExcludeExtension,
+    ExcludeGlob,
+    Gitignore(&'a GitignoreGlob),
+    GitignoreNegation(&'a GitignoreGlob),
+    Default,
+}
+
+impl Verdict<'_> {
+    fn decision(&self) -> Decision {
+        match self {
+            Verdict::Manual { include: true, .. }
+            | Verdict::IncludeGlob
+            | Verdict::IncludeExtension
+            | Verdict::GitignoreNegation(_)
+            | Verdict::Default => Decision::Include,
+            Verdict::HardExcluded
+            | Verdict::HardExcludeList(_)
+            | Verdict::SensitiveFile(_)
+            | Verdict::Manual { include: false, .. }
+            | Verdict::IncludeGlobMismatch
+            | Verdict::IncludeExtensionMismatch
+            | Verdict::ExcludeExtension
+            | Verdict::ExcludeGlob
+            | Verdict::Gitignore(_) => Decision::Exclude,
+        }
+    }
+}
+
+impl RuleEngine {
+    pub fn from_config(root: &Path, config: &ExportConfig) -> AppResult<Self> {
+        let hard_excludes = HardExcludes::from_config(config)?;
+        let gitignore = GitignoreSet::load_pruned(
+            root,
+            config.use_gitignore,
+            config.ignore_sources,
+            |rel_path| hard_excludes.prunes_dir(rel_path),
+        );
+        Self::with_gitignore(root, config, Arc::new(gitignore))
+    }
+
+    /// Builds the engine around an already loaded ignore set, so callers that cache the set
+    /// per root skip walking the tree again.
+    pub fn with_gitignore(
+        root: &Path,
+        config: &ExportConfig,
+        gitignore: Arc<GitignoreSet>,
+    ) -> AppResult<Self> {
+        let include_globs = compile_globset(&config.include_globs)?;
+        let exclude_globs = compile_globset(&config.exclude_globs)?;
+        let include_ext = normalize_extensions(&config.include_extensions);
+        let exclude_ext = normalize_extensions(&config.exclude_extensions);
+        let manual = normalize_manual_selections(&config.manual_selections);
+        let hard_excludes = HardExcludes::from_config(config)?;
+        let size_override_globs: Vec<String> = config
+            .size_overrides
+            .iter()
+            .map(|entry| entry.glob.clone())
+            .collect();
+        let size_overrides = compile_globset(&size_override_globs)?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            include_globs,
+            exclude_globs,
+            include_patterns: config.include_globs.clone(),
+            exclude_patterns: config.exclude_globs.clone(),
+            include_ext,
+            exclude_ext,
+            hard_excludes,
+            manual,
+            gitignore,
+            size_overrides,
+        })
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        self.gitignore.warnings()
+    }
+
+    pub fn gitignore(&self) -> &GitignoreSet {
+        &self.gitignore
+    }
+
+    pub fn should_include(&self, rel_path: &str, abs_path: &Path, is_dir: bool) -> Decision {
+        self.verdict(rel_path, abs_path, is_dir).decision()
+    }
+
+    /// Same evaluation as `should_include`, reporting which rule decided and what it matched.
+    pub fn explain(&self, rel_path: &str, abs_path: &Path, is_dir: bool) -> DecisionExplanation {
+        let verdict = self.verdict(rel_path, abs_path, is_dir);
+        let included = verdict.decision() == Decision::Include;
+        let mut source = None;
+        let (rule, pattern) = match &verdict {
+            Verdict::HardExcluded => (DecisionRule::HardExclude, Some(".git".to_string())),
+            Verdict::HardExcludeList(pattern) => {
+                (DecisionRule::HardExcludeList, Some(pattern.to_string()))
+            }
+            Verdict::SensitiveFile(pattern) => {
+                (DecisionRule::SensitiveFile, Some(pattern.to_string()))
+            }
+            Verdict::Manual { key, include } => {
+                let rule = if *include {
+                    DecisionRule::ManualInclude
+                } else {
+                    DecisionRule::ManualExclude
+                };
+                (rule, Some(key.to_string()))
+            }
+            Verdict::IncludeGlobMismatch => (DecisionRule::IncludeGlobMismatch, None),
+            Verdict::IncludeExtensionMismatch => (DecisionRule::IncludeExtensionMismatch, None),
+            Verdict::IncludeGlob => (
+                DecisionRule::IncludeGlob,
+                first_matching_pattern(
+                    self.include_globs.as_ref(),
+                    &self.include_patterns,
+                    rel_path,
+                ),
+            ),
+            Verdict::IncludeExtension => (
+                DecisionRule::IncludeExtension,
+                matching_extension(&self.include_ext, rel_path),
+            ),
+            Verdict::ExcludeExtension => (
+                DecisionRule::ExcludeExtension,
+                matching_extension(&self.exclude_ext, rel_path),
+            ),
+            Verdict::ExcludeGlob => (
+                DecisionRule::ExcludeGlob,
+                first_matching_pattern(
+                    self.exclude_globs.as_ref(),
+                    &self.exclude_patterns,
+                    rel_path,
+                ),
+            ),
+            Verdict::Gitignore(glob) | Verdict::GitignoreNegation(glob) => {
+                source = gitignore_source(glob, &self.root);
+                let rule = if matches!(verdict, Verdict::Gitignore(_)) {
+                    DecisionRule::Gitignore
+                } else {
+                    DecisionRule::GitignoreNegation
+                };
+                (rule, Some(glob.original().to_string()))
+            }
+            Verdict::Default => (DecisionRule::Default, None),
+        };
+
+        DecisionExplanation {
+            path: normalize_key(rel_path),
+            is_dir,
+            included,
+            rule,
+            summary: summarize(rule, pattern.as_deref(), source.as_ref()),
+            pattern,
+            source,
+        }
+    }
+
+    /// Index of the first entry in `ExportConfig::size_overrides` whose glob matches the path.
+    pub fn size_override(&self, rel_path: &str) -> Option<usize> {
+        self.size_overrides
+            .as_ref()?
+            .matches(rel_path)
+            .into_iter()
+            .min()
+    }
+
+    /// Whether nothing at or below the directory can be selected, so a walk can skip it whole:
+    /// it is hard-excluded, or ignored and out of reach of every include rule.
+    pub fn prunes_dir(&self, rel_path: &str, abs_path: &Path) -> bool {
+        if self.hard_excludes.prunes_dir(rel_path) {
+            return true;
+        }
+        let key = normalize_key(rel_path);
+        let manually_included = self.manual.iter().any(|(manual_key, state)| {
+            *state == ManualSelectionState::Include
+                && (is_same_or_under(manual_key, &key) || is_same_or_under(&key, manual_key))
+        });
+        self.include_globs.is_none()
+            && self.include_ext.is_empty()
+            && !manually_included
+            && self.gitignore.matched(abs_path, true).is_ignore()
+    }
+
+    /// Manually included paths whose file name is on the sensitive list; only these can select
+    /// a sensitive file.
+    pub fn sensitive_manual_includes(&self) -> impl Iterator<Item = &str> {
+        self.manual
+            .iter()
+            .filter(|(key, state)| {
+                **state == ManualSelectionState::Include && sensitive_file_match(key).is_some()
+            })
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Whether the file name is on the sensitive list, whatever the decision for it.
+    pub fn is_sensitive(&self, rel_path: &str) -> bool {
+        sensitive_file_match(rel_path).is_some()
+    }
+
+    fn verdict(&self, rel_path: &str, abs_path: &Path, is_dir: bool) -> Verdict<'_> {
+        if is_hard_excluded(rel_path) {
+            return Verdict::HardExcluded;
+        }
+
+        let manual = self.manual_state_for(rel_path);
+        if let Some((prefix, pattern)) = self.hard_excludes.matched(rel_path) {
+            let manually_included = matches!(
+                &manual,
+                Some((key, ManualSelectionState::Include)) if is_same_or_under(key, &prefix)
+            );
+            if !manually_included {
+                return Verdict::HardExcludeList(pattern);
+            }
+        }
+
+        if !is_dir {
+            if let Some(pattern) = sensitive_file_match(rel_path) {
+                let key = normalize_key(rel_path);
+                let manually_included = matches!(
+                    &manual,
+                    Some((manual_key, ManualSelectionState::Include)) if *manual_key == key
+                );
+                if !manually_included {
+                    return Verdict::SensitiveFile(pattern);
+                }
+            }
+        }
+
+        if let Some((key, manual_state)) = manual {
+            match manual_state {
+                ManualSelectionState::Include => return Verdict::Manual { key, include: true },
+                ManualSelectionState::Exclude => {
+                    return Verdict::Manual {
+                        key,
+                        include: false,
+                    }
+                }
+                ManualSelectionState::Inherit => {}
+            }
+        }
+
+        let include_glob_match = self.matches_include_glob(rel_path);
+        if matches!(include_glob_match, Some(false)) {
+            return Verdict::IncludeGlobMismatch;
+        }
+
+        let include_ext_match = if is_dir {
+            None
+        } else {
+            self.matches_include_extension(rel_path)
+        };
+        if matches!(include_ext_match, Some(false)) {
+            return Verdict::IncludeExtensionMismatch;
+        }
+
+        if matches!(include_glob_match, Some(true)) {
+            return Verdict::IncludeGlob;
+        }
+        if matches!(include_ext_match, Some(true)) {
+            return Verdict::IncludeExtension;
+        }
+
+        if !is_dir && self.matches_exclude_extension(rel_path) {
+            return Verdict::ExcludeExtension;
+        }
+
+        if self.matches_exclude_glob(rel_path) {
+            return Verdict::ExcludeGlob;
+        }
+
+        match self.gitignore.matched(abs_path, is_dir) {
+            Match::Ignore(glob) => return Verdict::Gitignore(glob),
+            Match::Whitelist(glob) => return Verdict::GitignoreNegation(glob),
+            Match::None => {}
+        }
+
+        Verdict::Default
+    }
+
+    fn matches_include_glob(&self, rel_path: &str) -> Option<bool> {
+        self.include_globs
+            .as_ref()
+            .map(|set| set.is_match(rel_path))
+    }
+
+    fn matches_exclude_glob(&self, rel_path: &str) -> bool {
+        if let Some(set) = &self.exclude_globs {
+            return set.is_match(rel_path);
+        }
+        false
+    }
+
+    fn matches_include_extension(&self, rel_path: &str) -> Option<bool> {
+        if self.include_ext.is_empty() {
+            return None;
+        }
+        let lower = rel_path.to_lowercase();
+        Some(self.include_ext.iter().any(|ext| lower.ends_with(ext)))
+    }
+
+    fn matches_exclude_extension(&self, rel_path: &str) -> bool {
+        if self.exclude_ext.is_empty() {
+            return false;
+        }
+        let lower = rel_path.to_lowercase();
+        self.exclude_ext.iter().any(|ext| lower.ends_with(ext))
+    }
+
+    fn manual_state_for(&self, rel_path: &str) -> Option<(&str, ManualSelectionState)> {
+        let key = normalize_key(rel_path);
+        if let Some((manual_key, state)) = self.manual.get_key_value(&key) {
+            return Some((manual_key.as_str(), state.clone()));
+        }
+
+        let mut best: Option<(&str, ManualSelectionState)> = None;
+        for (manual_key, state) in &self.manual {
+            if key == *manual_key
+                || (key.starts_with(manual_key) && key.chars().nth(manual_key.len()) == Some('/'))
+            {
+                if let Some((best_key, _)) = &best {
+                    if manual_key.len() <= best_key.len() {
+                        continue;
+                    }
+                }
+                best = Some((manual_key.as_str(), state.clone()));
+            }
+        }
+        best
+    }
+}
+
+/// `ExportConfig::hard_excludes` compiled, with the manual includes that can reach below them.
+pub struct HardExcludes {
+    set: Option<GlobSet>,
+    patterns: Vec<String>,
+    manual_includes: Vec<String>,
+}
+
+impl HardExcludes {
+    /// Entries without a `/` are matched as a name at any depth; `*` never crosses a `/`.
+    pub fn from_config(config: &ExportConfig) -> AppResult<Self> {
+        let mut builder = GlobSetBuilder::new();
+        let mut patterns = Vec::new();
+        for entry in &config.hard_excludes {
+            let normalized = normalize_key(entry);
+            if normalized.is_empty() {
+                continue;
+            }
+            let pattern = if normalized.contains('/') {
+                normalized.clone()
+            } else {
+                format!("**/{normalized}")
+            };
+            let glob = GlobBuilder::new(&pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| AppError::RuleInvalidGlob {
+                    glob: Some(entry.clone()),
+                    reason: e.kind().to_string(),
+                })?;
+            builder.add(glob);
+            patterns.push(normalized);
+        }
+        let set = if patterns.is_empty() {
+            None
+        } else {
+            Some(builder.build().map_err(|e| AppError::RuleInvalidGlob {
+                glob: None,
+                reason: e.to_string(),
+            })?)
+        };
+        let manual_includes = config
+            .manual_selections
+            .iter()
+            .filter(|(_, state)| **state == ManualSelectionState::Include)
+            .map(|(key, _)| normalize_key(key))
+            .collect();
+        Ok(Self {
+            set,
+            patterns,
+            manual_includes,
+        })
+    }
+
+    /// The shallowest prefix of `rel_path` that an entry matches, with that entry.
+    fn matched(&self, rel_path: &str) -> Option<(String, &str)> {
+        let set = self.set.as_ref()?;
+        let key = normalize_key(rel_path);
+        let ends = key
+            .match_indices('/')
+            .map(|(index, _)| index)
+            .chain([key.len()]);
+        for end in ends {
+            let prefix = &key[..end];
+            if let Some(index) = set.matches(prefix).into_iter().next() {
+                return Some((prefix.to_string(), self.patterns[index].as_str()));
+            }
+        }
+        None
+    }
+
+    /// Whether every file below the directory is excluded: it is `.git`, or an entry matches it
+    /// and no manual include at or below the match leads into it.
+    pub fn prunes_dir(&self, rel_path: &str) -> bool {
+        if is_hard_excluded(rel_path) {
+            return true;
+        }
+        let Some((prefix, _)) = self.matched(rel_path) else {
+            return false;
+        };
+        let key = normalize_key(rel_path);
+        !self.manual_includes.iter().any(|include| {
+            is_same_or_under(include, &prefix)
+                && (is_same_or_under(include, &key) || is_same_or_under(&key, include))
+        })
+    }
+}
+
+fn first_matching_pattern(
+    set: Option<&GlobSet>,
+    patterns: &[String],
+    rel_path: &str,
+) -> Option<String> {
+    let index = set?.matches(rel_path).into_iter().next()?;
+    patterns.get(index).cloned()
+}
+
+fn matching_extension(extensions: &HashSet<String>, rel_path: &str) -> Option<String> {
+    let lower = rel_path.to_lowercase();
+    extensions
+        .iter()
+        .filter(|ext| lower.ends_with(ext.as_str()))
+        .max_by_key(|ext| ext.len())
+        .cloned()
+}
+
+/// `ignore` keeps the file a pattern came from but not its line, so the line is looked up again;
+/// the last identical line wins, as it would in git. Files under the root are reported relative.
+fn gitignore_source(glob: &GitignoreGlob, root: &Path) -> Option<RuleSource> {
+    let file = glob.from()?;
+    let line = fs::read_to_string(file).ok().and_then(|text| {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| line.trim_end() == glob.original())
+            .map(|(index, _)| index + 1)
+            .last()
+    });
+    Some(RuleSource {
+        file: file
+            .strip_prefix(root)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        line,
+    })
+}
+
+fn summarize(rule: DecisionRule, pattern: Option<&str>, source: Option<&RuleSource>) -> String {
+    let pattern = pattern.unwrap_or_default();
+    match rule {
+        DecisionRule::HardExclude => "Always excluded: inside the .git directory".to_string(),
+        DecisionRule::HardExcludeList => {
+            format!("Excluded by hard-exclude entry '{pattern}'; include it manually to export it")
+        }
+        DecisionRule::SensitiveFile => format!(
+            "Excluded as a sensitive file ('{pattern}'); include the file itself manually and \
+             confirm the export to export it"
+        ),
+        DecisionRule::ManualInclude => format!("Included by manual selection of '{pattern}'"),
+        DecisionRule::ManualExclude => format!("Excluded by manual selection of '{pattern}'"),
+        DecisionRule::IncludeGlobMismatch => {
+            "Excluded: matches none of the include globs".to_string()
+        }
+        DecisionRule::IncludeExtensionMismatch => {
+            "Excluded: has none of the include extensions".to_string()
+        }
+        DecisionRule::IncludeGlob => format!("Included by include glob '{pattern}'"),
+        DecisionRule::IncludeExtension => format!("Included by include extension '{pattern}'"),
+        DecisionRule::ExcludeExtension => format!("Excluded by exclude extension '{pattern}'"),
+        DecisionRule::ExcludeGlob => format!("Excluded by exclude glob '{pattern}'"),
+        DecisionRule::Gitignore | DecisionRule::GitignoreNegation => {
+            let verb = if rule == DecisionRule::Gitignore {
+                "Excluded"
+            } else {
+                "Included"
+            };
+            match source {
+                Some(RuleSource {
+                    file,
+                    line: Some(line),
+                }) => format!("{verb} by gitignore pattern '{pattern}' ({file}:{line})"),
+                Some(RuleSource { file, line: None }) => {
+                    format!("{verb} by gitignore pattern '{pattern}' ({file})")
+                }
+                None => format!("{verb} by gitignore pattern '{pattern}'"),
+            }
+        }
+        DecisionRule::Default => "Included: no rule excludes it".to_string(),
+    }
+}
+
+pub fn is_hard_excluded(rel_path: &str) -> bool {
+    let normalized = normalize_key(rel_path);
+    normalized == ".git" || normalized.starts_with(".git/")
+}
+
+fn sensitive_file_match(rel_path: &str) -> Option<&'static str> {
+    let set = SENSITIVE_FILES.get_or_init(|| {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in SENSITIVE_FILE_PATTERNS {
+            builder.add(Glob::new(pattern).expect("sensitive file pattern"));
+        }
+        builder.build().expect("sensitive file patterns")
+    });
+    let key = normalize_key(rel_path);
+    let name = key.rsplit('/').next().unwrap_or_default();
+    let index = set.matches(name).into_iter().next()?;
+    Some(SENSITIVE_FILE_PATTERNS[index])
+}
+
+fn is_same_or_under(key: &str, prefix: &str) -> bool {
+    key == prefix || (key.starts_with(prefix) && key[prefix.len()..].starts_with('/'))
+}
+
+fn normalize_manual_selections(
+    source: &BTreeMap<String, ManualSelectionState>,
+) -> BTreeMap<String, ManualSelectionState> {
+    source
+        .iter()
+        .map(|(k, v)| (normalize_key(k), v.clone()))
+        .collect()
+}
+
+fn normalize_extensions(items: &[String]) -> HashSet<String> {
+    items
+        .iter()
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            if v.starts_with('.') {
+                v
+            } else {
+                format!(".{v}")
+            }
+        })
+        .collect()
+}
+
+fn compile_globset(patterns: &[String]) -> AppResult<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| AppError::RuleInvalidGlob {
+            glob: Some(pattern.clone()),
+            reason: e.kind().to_string(),
+        })?;
+        builder.add(glob);
+    }
+    let set = builder.build().map_err(|e| AppError::RuleInvalidGlob {
+        glob: None,
+        reason: e.to_string(),
+    })?;
+    Ok(Some(set))
+}
+
+fn normalize_key(input: &str) -> String {
+    input
+        .trim()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .trim_matches('/')
+        .to_string()
+}
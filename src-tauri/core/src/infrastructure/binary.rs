@@ -0,0 +1,100 @@
+use std::io::{self, Read};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+const FALLBACK_MIME: &str = "application/octet-stream";
+const HEX_DUMP_WIDTH: usize = 16;
+const BASE64_LINE_WIDTH: usize = 76;
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// MIME type from the file's magic bytes; `application/octet-stream` when none match.
+pub fn sniff_mime(probe: &[u8]) -> &'static str {
+    infer::get(probe)
+        .map(|kind| kind.mime_type())
+        .unwrap_or(FALLBACK_MIME)
+}
+
+/// Lowercase hex SHA-256 of everything `reader` yields.
+pub fn sha256_hex(reader: &mut impl Read) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// `hexdump -C` style lines: offset, sixteen bytes in two groups, and their printable ASCII.
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_DUMP_WIDTH)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let mut hex = String::with_capacity(HEX_DUMP_WIDTH * 3 + 1);
+            for column in 0..HEX_DUMP_WIDTH {
+                if column == HEX_DUMP_WIDTH / 2 {
+                    hex.push(' ');
+                }
+                match chunk.get(column) {
+                    Some(byte) => hex.push_str(&format!("{byte:02x} ")),
+                    None => hex.push_str("   "),
+                }
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| match byte {
+                    b' '..=b'~' => *byte as char,
+                    _ => '.',
+                })
+                .collect();
+            format!("{:08x}  {hex} |{ascii}|", row * HEX_DUMP_WIDTH)
+        })
+        .collect()
+}
+
+/// Standard base64 wrapped at 76 columns, as in MIME.
+pub fn base64_dump(bytes: &[u8]) -> Vec<String> {
+    let encoded = STANDARD.encode(bytes);
+    encoded
+        .as_bytes()
+        .chunks(BASE64_LINE_WIDTH)
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_dump, hex_dump, sha256_hex, sniff_mime};
+
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn sniffs_hashes_and_dumps_bytes() {
+        assert_eq!(sniff_mime(PNG_SIGNATURE), "image/png");
+        assert_eq!(sniff_mime(b"\0\x01\x02\x03"), "application/octet-stream");
+        assert_eq!(
+            sha256_hex(&mut &b"abc"[..]).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        assert_eq!(
+            hex_dump(PNG_SIGNATURE),
+            vec!["00000000  89 50 4e 47 0d 0a 1a 0a  00 00 00 0d 49 48 44 52  |.PNG........IHDR|"]
+        );
+        assert_eq!(
+            hex_dump(b"hi"),
+            vec![format!("00000000  68 69 {}|hi|", " ".repeat(44))]
+        );
+        assert_eq!(base64_dump(&[0u8; 60]), vec!["A".repeat(76), "A".repeat(4)]);
+    }
+}
@@ -0,0 +1,209 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::infrastructure::errors::{AppError, AppResult};
+use crate::models::EncodingOverride;
+
+/// Share of code units that must have a NUL high byte before BOM-less text is read as UTF-16.
+const MIN_UTF16_NUL_RATIO: f64 = 0.3;
+const MIN_UTF16_UNITS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingSource {
+    ByteOrderMark,
+    Detected,
+    /// Index into `ExportConfig::encoding_overrides`.
+    Override(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEncoding {
+    pub encoding: &'static Encoding,
+    pub source: EncodingSource,
+}
+
+impl FileEncoding {
+    pub fn is_utf8(&self) -> bool {
+        self.encoding == UTF_8
+    }
+
+    /// UTF-16 text is full of NUL bytes, so binary detection must not see it.
+    pub fn is_utf16(&self) -> bool {
+        self.encoding == UTF_16LE || self.encoding == UTF_16BE
+    }
+}
+
+/// `ExportConfig::encoding_overrides`, compiled; the first matching glob wins.
+pub struct EncodingOverrides {
+    globs: Option<GlobSet>,
+    encodings: Vec<&'static Encoding>,
+}
+
+impl EncodingOverrides {
+    pub fn new(overrides: &[EncodingOverride]) -> AppResult<Self> {
+        if overrides.is_empty() {
+            return Ok(Self {
+                globs: None,
+                encodings: Vec::new(),
+            });
+        }
+        let mut builder = GlobSetBuilder::new();
+        let mut encodings = Vec::with_capacity(overrides.len());
+        for entry in overrides {
+            let glob = Glob::new(&entry.glob).map_err(|e| AppError::RuleInvalidGlob {
+                glob: Some(entry.glob.clone()),
+                reason: e.kind().to_string(),
+            })?;
+            builder.add(glob);
+            let encoding =
+                Encoding::for_label(entry.encoding.trim().as_bytes()).ok_or_else(|| {
+                    AppError::UnknownEncoding {
+                        label: entry.encoding.clone(),
+                    }
+                })?;
+            encodings.push(encoding);
+        }
+        let set = builder.build().map_err(|e| AppError::RuleInvalidGlob {
+            glob: None,
+            reason: e.to_string(),
+        })?;
+        Ok(Self {
+            globs: Some(set),
+            encodings,
+        })
+    }
+
+    pub fn for_path(&self, rel_path: &str) -> Option<FileEncoding> {
+        let index = self.globs.as_ref()?.matches(rel_path).into_iter().min()?;
+        Some(FileEncoding {
+            encoding: self.encodings[index],
+            source: EncodingSource::Override(index),
+        })
+    }
+}
+
+/// Picks the encoding of a file from its first bytes: a byte order mark, then BOM-less UTF-16
+/// (ASCII-heavy text with every other byte NUL), then UTF-8 when the bytes are valid, and
+/// otherwise chardetng's statistical guess. `complete` is set when `probe` is the whole file.
+pub fn detect(probe: &[u8], complete: bool) -> FileEncoding {
+    if let Some((encoding, _)) = Encoding::for_bom(probe) {
+        return FileEncoding {
+            encoding,
+            source: EncodingSource::ByteOrderMark,
+        };
+    }
+    let encoding = utf16_without_bom(probe).unwrap_or_else(|| {
+        if is_utf8(probe, complete) {
+            UTF_8
+        } else {
+            let mut detector = EncodingDetector::new();
+            detector.feed(probe, complete);
+            detector.guess(None, true)
+        }
+    });
+    FileEncoding {
+        encoding,
+        source: EncodingSource::Detected,
+    }
+}
+
+fn is_utf8(probe: &[u8], complete: bool) -> bool {
+    match std::str::from_utf8(probe) {
+        Ok(_) => true,
+        // The probe may end inside a character.
+        Err(error) => !complete && error.error_len().is_none(),
+    }
+}
+
+fn utf16_without_bom(probe: &[u8]) -> Option<&'static Encoding> {
+    let units = probe.len() / 2;
+    if units < MIN_UTF16_UNITS {
+        return None;
+    }
+    // ASCII text in UTF-16 is a NUL high byte next to a printable low byte.
+    let ascii_units = |high: usize| {
+        let mut count = 0usize;
+        for unit in probe.chunks_exact(2) {
+            match (unit[high], unit[1 - high]) {
+                (0, low) if is_text_byte(low) => count += 1,
+                (0, _) => return None,
+                _ => {}
+            }
+        }
+        Some(count)
+    };
+    let threshold = (units as f64 * MIN_UTF16_NUL_RATIO).ceil() as usize;
+    if ascii_units(1).is_some_and(|count| count >= threshold) {
+        Some(UTF_16LE)
+    } else if ascii_units(0).is_some_and(|count| count >= threshold) {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+fn is_text_byte(byte: u8) -> bool {
+    matches!(byte, b'\t' | b'\n' | b'\r' | b' '..=b'~')
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding_rs::{SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+    use crate::models::EncodingOverride;
+
+    use super::{detect, EncodingOverrides, EncodingSource};
+
+    #[test]
+    fn detects_bom_utf16_legacy_and_utf8_text() {
+        let bom = detect(b"\xFF\xFEh\0i\0", true);
+        assert_eq!(
+            (bom.encoding, bom.source),
+            (UTF_16LE, EncodingSource::ByteOrderMark)
+        );
+        assert_eq!(detect(b"\0h\0e\0l\0l\0o", true).encoding, UTF_16BE);
+
+        let (sjis, _, _) = SHIFT_JIS.encode("// 日本語のコメントです。設定を読み込みます。\n");
+        assert_eq!(detect(&sjis, true).encoding, SHIFT_JIS);
+        assert_eq!(detect("名前=値\n".as_bytes(), true).encoding, UTF_8);
+        assert_eq!(
+            detect(b"caf\xE9 cr\xE8me br\xFBl\xE9e\n", true).encoding,
+            WINDOWS_1252
+        );
+        // A probe cut inside a UTF-8 character is still UTF-8.
+        assert_eq!(detect(&"é".as_bytes()[..1], false).encoding, UTF_8);
+    }
+
+    #[test]
+    fn first_matching_override_wins() {
+        let overrides = EncodingOverrides::new(&[
+            EncodingOverride {
+                glob: "legacy/**".to_string(),
+                encoding: "shift_jis".to_string(),
+            },
+            EncodingOverride {
+                glob: "*.properties".to_string(),
+                encoding: "latin1".to_string(),
+            },
+        ])
+        .unwrap();
+
+        let legacy = overrides.for_path("legacy/app.properties").unwrap();
+        assert_eq!(
+            (legacy.encoding, legacy.source),
+            (SHIFT_JIS, EncodingSource::Override(0))
+        );
+        assert_eq!(
+            overrides.for_path("app.properties").unwrap().encoding,
+            WINDOWS_1252
+        );
+        assert!(overrides.for_path("src/main.rs").is_none());
+
+        let unknown = EncodingOverrides::new(&[EncodingOverride {
+            glob: "*".to_string(),
+            encoding: "klingon".to_string(),
+        }]);
+        assert!(unknown.is_err());
+    }
+}
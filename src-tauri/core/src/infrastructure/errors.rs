@@ -0,0 +1,275 @@
+use std::fmt;
+use std::io;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Every failure the core API reports. Each variant owns one stable `E_*` code; the desktop app
+/// receives it as `{code, message, details}` and `Display` renders `[CODE] message`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    RootRequired,
+    RootInvalid {
+        path: String,
+        kind: io::ErrorKind,
+        reason: String,
+    },
+    RootNotDir {
+        path: String,
+    },
+    PathOutsideRoot {
+        path: String,
+    },
+    DirPathNotDir {
+        path: String,
+    },
+    OutputRequired,
+    OutputIsDir {
+        path: String,
+    },
+    OutputExists {
+        path: String,
+    },
+    IoRead {
+        context: String,
+        path: Option<String>,
+        kind: io::ErrorKind,
+        reason: String,
+    },
+    IoWrite {
+        context: String,
+        path: Option<String>,
+        kind: io::ErrorKind,
+        reason: String,
+    },
+    RuleInvalidGlob {
+        glob: Option<String>,
+        reason: String,
+    },
+    SensitiveUnconfirmed {
+        paths: Vec<String>,
+    },
+    ExportCancelled,
+    UnknownEncoding {
+        label: String,
+    },
+    /// A failure outside the engine's own error handling, such as a panicked worker.
+    Internal {
+        context: String,
+        reason: String,
+    },
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::RootRequired => "E_ROOT_REQUIRED",
+            Self::RootInvalid { .. } => "E_ROOT_INVALID",
+            Self::RootNotDir { .. } => "E_ROOT_NOT_DIR",
+            Self::PathOutsideRoot { .. } => "E_PATH_OUTSIDE_ROOT",
+            Self::DirPathNotDir { .. } => "E_DIRPATH_NOT_DIR",
+            Self::OutputRequired => "E_OUTPUT_REQUIRED",
+            Self::OutputIsDir { .. } => "E_OUTPUT_IS_DIR",
+            Self::OutputExists { .. } => "E_OUTPUT_EXISTS",
+            Self::IoRead { .. } => "E_IO_READ",
+            Self::IoWrite { .. } => "E_IO_WRITE",
+            Self::RuleInvalidGlob { .. } => "E_RULE_INVALID_GLOB",
+            Self::SensitiveUnconfirmed { .. } => "E_SENSITIVE_UNCONFIRMED",
+            Self::ExportCancelled => "E_EXPORT_CANCELLED",
+            Self::UnknownEncoding { .. } => "E_ENCODING_UNKNOWN",
+            Self::Internal { .. } => "E_INTERNAL",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::RootRequired => "rootPath is required".to_string(),
+            Self::RootInvalid { path, reason, .. } => {
+                format!("Invalid rootPath '{path}': {reason}")
+            }
+            Self::RootNotDir { path } => format!("rootPath must be a directory: '{path}'"),
+            Self::PathOutsideRoot { path } => format!("Path is outside of rootPath: '{path}'"),
+            Self::DirPathNotDir { path } => format!("dirPath must be a directory: '{path}'"),
+            Self::OutputRequired => "outputPath is required".to_string(),
+            Self::OutputIsDir { path } => {
+                format!("outputPath must be a file path, not a directory: '{path}'")
+            }
+            Self::OutputExists { path } => format!("Output file already exists: '{path}'"),
+            Self::IoRead {
+                context,
+                path,
+                reason,
+                ..
+            }
+            | Self::IoWrite {
+                context,
+                path,
+                reason,
+                ..
+            } => match path {
+                Some(path) => format!("{context} '{path}': {reason}"),
+                None => format!("{context}: {reason}"),
+            },
+            Self::RuleInvalidGlob {
+                glob: Some(glob),
+                reason,
+            } => format!("Invalid glob '{glob}': {reason}"),
+            Self::RuleInvalidGlob { glob: None, reason } => {
+                format!("Failed to build glob matcher: {reason}")
+            }
+            Self::SensitiveUnconfirmed { paths } => format!(
+                "Export includes sensitive files and was not confirmed: '{}'",
+                paths.join("', '")
+            ),
+            Self::ExportCancelled => "Export was cancelled; no output was written".to_string(),
+            Self::UnknownEncoding { label } => format!("Unknown encoding '{label}'"),
+            Self::Internal { context, reason } => format!("{context}: {reason}"),
+        }
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Self::RootInvalid { path, .. }
+            | Self::RootNotDir { path }
+            | Self::PathOutsideRoot { path }
+            | Self::DirPathNotDir { path }
+            | Self::OutputIsDir { path }
+            | Self::OutputExists { path } => Some(path),
+            Self::IoRead { path, .. } | Self::IoWrite { path, .. } => path.as_deref(),
+            Self::RootRequired
+            | Self::OutputRequired
+            | Self::RuleInvalidGlob { .. }
+            | Self::SensitiveUnconfirmed { .. }
+            | Self::ExportCancelled
+            | Self::UnknownEncoding { .. }
+            | Self::Internal { .. } => None,
+        }
+    }
+
+    pub fn io_kind(&self) -> Option<io::ErrorKind> {
+        match self {
+            Self::RootInvalid { kind, .. }
+            | Self::IoRead { kind, .. }
+            | Self::IoWrite { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// Attaches `path` to an I/O error raised by a helper that did not know which file it was on.
+    pub fn at_path(mut self, new_path: &str) -> Self {
+        if let Self::IoRead { path, .. } | Self::IoWrite { path, .. } = &mut self {
+            if path.is_none() {
+                *path = Some(new_path.to_string());
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.message())?;
+        state.serialize_field("details", &ErrorDetails::from(self))?;
+        state.end()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorDetails<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    glob: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    io_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paths: Option<&'a [String]>,
+}
+
+impl<'a> From<&'a AppError> for ErrorDetails<'a> {
+    fn from(error: &'a AppError) -> Self {
+        let glob = match error {
+            AppError::RuleInvalidGlob { glob, .. } => glob.as_deref(),
+            _ => None,
+        };
+        let paths = match error {
+            AppError::SensitiveUnconfirmed { paths } => Some(paths.as_slice()),
+            _ => None,
+        };
+        Self {
+            path: error.path(),
+            glob,
+            io_kind: error.io_kind().map(|kind| format!("{kind:?}")),
+            paths,
+        }
+    }
+}
+
+pub fn read_error(context: &str, error: io::Error) -> AppError {
+    AppError::IoRead {
+        context: context.to_string(),
+        path: None,
+        kind: error.kind(),
+        reason: error.to_string(),
+    }
+}
+
+pub fn write_error(context: &str, error: io::Error) -> AppError {
+    AppError::IoWrite {
+        context: context.to_string(),
+        path: None,
+        kind: error.kind(),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::{read_error, AppError};
+
+    #[test]
+    fn serializes_code_message_and_details() {
+        let error = read_error(
+            "Failed to read file",
+            io::Error::from(io::ErrorKind::PermissionDenied),
+        )
+        .at_path("src/main.rs");
+        let value = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(value["code"], "E_IO_READ");
+        assert!(value["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to read file 'src/main.rs': "));
+        assert_eq!(value["details"]["path"], "src/main.rs");
+        assert_eq!(value["details"]["ioKind"], "PermissionDenied");
+        assert!(value["details"].get("glob").is_none());
+    }
+
+    #[test]
+    fn display_keeps_bracketed_code_prefix() {
+        let error = AppError::RuleInvalidGlob {
+            glob: Some("src/[".to_string()),
+            reason: "unclosed character class".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "[E_RULE_INVALID_GLOB] Invalid glob 'src/[': unclosed character class"
+        );
+    }
+}
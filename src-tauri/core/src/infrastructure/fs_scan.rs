@@ -1,34 +1,30 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use ignore::gitignore::Gitignore;
-use ignore::Match;
-
-use crate::infrastructure::errors::read_error;
+use crate::infrastructure::errors::{read_error, AppError, AppResult};
+use crate::infrastructure::gitignore::GitignoreSet;
+use crate::infrastructure::pathing::display_path;
 use crate::infrastructure::sorting::compare_entries;
-use crate::models::{ScanLimits, TreeNode};
-
-#[derive(Debug, Clone)]
-pub struct ScanBatch {
-    pub nodes: Vec<TreeNode>,
-    pub warnings: Vec<String>,
-}
+use crate::models::{ScanBatch, ScanLimits, TreeNode};
 
 pub fn scan_single_level(
     root: &Path,
     dir: &Path,
     limits: &ScanLimits,
-    gitignore: Option<&Gitignore>,
-) -> Result<ScanBatch, String> {
+    gitignore: &GitignoreSet,
+) -> AppResult<ScanBatch> {
     let mut entries: Vec<(PathBuf, bool)> = Vec::new();
     let mut warnings = Vec::new();
 
-    let reader = fs::read_dir(dir).map_err(|e| read_error("Failed to read directory", e))?;
+    let dir_label = display_path(dir);
+    let reader = fs::read_dir(dir)
+        .map_err(|e| read_error("Failed to read directory", e).at_path(&dir_label))?;
     for item in reader {
-        let item = item.map_err(|e| read_error("Failed to read directory entry", e))?;
-        let file_type = item
-            .file_type()
-            .map_err(|e| read_error("Failed to read file type", e))?;
+        let item =
+            item.map_err(|e| read_error("Failed to read directory entry", e).at_path(&dir_label))?;
+        let file_type = item.file_type().map_err(|e| {
+            read_error("Failed to read file type", e).at_path(&display_path(&item.path()))
+        })?;
         entries.push((item.path(), file_type.is_dir()));
         if entries.len() >= limits.max_files {
             warnings.push(format!(
@@ -43,13 +39,12 @@ pub fn scan_single_level(
 
     let mut nodes = Vec::with_capacity(entries.len());
     for (entry_path, is_dir) in entries {
-        let ignored_by_gitignore = matches!(
-            gitignore.map(|matcher| matcher.matched_path_or_any_parents(&entry_path, is_dir)),
-            Some(Match::Ignore(_))
-        );
+        let ignored_by_gitignore = gitignore.matched(&entry_path, is_dir).is_ignore();
         let rel = entry_path
             .strip_prefix(root)
-            .map_err(|_| read_error("Failed to derive relative path", "path not under root"))?;
+            .map_err(|_| AppError::PathOutsideRoot {
+                path: display_path(&entry_path),
+            })?;
         let rel_text = rel.to_string_lossy().replace('\\', "/");
         let name = entry_path
             .file_name()
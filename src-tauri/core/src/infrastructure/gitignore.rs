@@ -0,0 +1,612 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ignore::gitignore::{gitconfig_excludes_path, Gitignore, GitignoreBuilder, Glob};
+use ignore::Match;
+use walkdir::WalkDir;
+
+use crate::infrastructure::pathing::relative_unix_path;
+use crate::models::IgnoreSources;
+
+/// Project-specific ignore file, read regardless of `IgnoreSources`.
+pub const PROJECT_IGNORE_FILE: &str = ".codebasetotxtignore";
+
+/// Ignore files found inside the root, lowest precedence first, paired with whether the
+/// configuration reads them.
+fn in_tree_files(use_gitignore: bool, sources: IgnoreSources) -> [(&'static str, bool); KINDS] {
+    [
+        (".gitignore", use_gitignore),
+        (".ignore", sources.dot_ignore),
+        (".rgignore", sources.rgignore),
+        (PROJECT_IGNORE_FILE, true),
+    ]
+}
+
+const KINDS: usize = 4;
+/// Index of `.gitignore` in `in_tree_files`.
+const GITIGNORE: usize = 0;
+
+/// Every ignore file that applies to a root, each compiled relative to its own directory, plus
+/// what is needed to tell whether they are still current. When the root is inside a git work
+/// tree, the `.gitignore` files of the directories above it up to the work tree apply too.
+///
+/// Matching follows git: a path is checked one component at a time from the root down, and
+/// once a directory is ignored nothing below it can be re-included. At each step the deepest
+/// file of a kind decides, and kinds are consulted from `.codebasetotxtignore` down to
+/// `.gitignore`, then `.git/info/exclude`, then the global excludes file.
+pub struct GitignoreSet {
+    root: PathBuf,
+    /// The enclosing work tree, or the root outside of one.
+    work_tree: PathBuf,
+    /// In-tree matchers by the directory holding them, indexed by kind.
+    by_dir: HashMap<PathBuf, [Option<Gitignore>; KINDS]>,
+    /// `.git/info/exclude`, then the global excludes file; both relative to the work tree.
+    fallback: Vec<Gitignore>,
+    warnings: Vec<String>,
+    fingerprint: Vec<Stamp>,
+}
+
+/// Modification state of a directory or ignore file seen while loading. Directory mtimes change
+/// when entries are added or removed, which catches new ignore files. Missing files are stamped
+/// too, so creating one is noticed.
+struct Stamp {
+    path: PathBuf,
+    /// Only paths under this directory can match differently once the stamp changes.
+    scope: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    fn read(path: &Path, scope: &Path) -> Self {
+        let (modified, len) = Self::state(path);
+        Self {
+            path: path.to_path_buf(),
+            scope: scope.to_path_buf(),
+            modified,
+            len,
+        }
+    }
+
+    fn state(path: &Path) -> (Option<SystemTime>, u64) {
+        let metadata = fs::metadata(path).ok();
+        (
+            metadata.as_ref().and_then(|m| m.modified().ok()),
+            metadata.map(|m| m.len()).unwrap_or(0),
+        )
+    }
+
+    fn is_current(&self) -> bool {
+        Self::state(&self.path) == (self.modified, self.len)
+    }
+}
+
+impl GitignoreSet {
+    pub fn load(root: &Path, use_gitignore: bool, sources: IgnoreSources) -> Self {
+        Self::load_pruned(root, use_gitignore, sources, |_| false)
+    }
+
+    /// Like `load`, but never descends into ignored directories, nor into those `skip_dir`
+    /// rejects by their path relative to the root; nothing below them can be selected, so
+    /// their ignore files would never be consulted.
+    pub fn load_pruned(
+        root: &Path,
+        use_gitignore: bool,
+        sources: IgnoreSources,
+        skip_dir: impl Fn(&str) -> bool,
+    ) -> Self {
+        let repo_root = find_repo_root(root);
+        let mut set = Self {
+            root: root.to_path_buf(),
+            work_tree: repo_root
+                .as_ref()
+                .map_or_else(|| root.to_path_buf(), |(dir, _)| dir.clone()),
+            by_dir: HashMap::new(),
+            fallback: Vec::new(),
+            warnings: Vec::new(),
+            fingerprint: Vec::new(),
+        };
+        let git_dir = repo_root.map(|(_, git_dir)| git_dir);
+        set.load_fallback(git_dir.as_deref(), use_gitignore, sources);
+        if use_gitignore {
+            set.load_parent_gitignores();
+        }
+
+        // A directory's ignore files are read as soon as the walk enters it, so they are in
+        // place before any of its subdirectories is checked.
+        let wanted = in_tree_files(use_gitignore, sources);
+        let mut walker = WalkDir::new(root).sort_by_file_name().into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            let dir = entry.path();
+            if entry.depth() > 0
+                && (entry.file_name() == ".git"
+                    || relative_unix_path(root, dir).is_ok_and(|rel_path| skip_dir(&rel_path))
+                    || set.matched(dir, true).is_ignore())
+            {
+                walker.skip_current_dir();
+                continue;
+            }
+            set.fingerprint.push(Stamp::read(dir, dir));
+            for (kind, (name, enabled)) in wanted.iter().enumerate() {
+                let file = dir.join(name);
+                if !enabled || !file.is_file() {
+                    continue;
+                }
+                set.fingerprint.push(Stamp::read(&file, dir));
+                if let Some(matcher) = set.build_matcher(dir, &file) {
+                    set.by_dir.entry(dir.to_path_buf()).or_default()[kind] = Some(matcher);
+                }
+            }
+        }
+
+        set
+    }
+
+    fn load_fallback(
+        &mut self,
+        git_dir: Option<&Path>,
+        use_gitignore: bool,
+        sources: IgnoreSources,
+    ) {
+        let root = self.root.clone();
+        let base = self.work_tree.clone();
+        let mut outside = Vec::new();
+        if use_gitignore && sources.git_info_exclude {
+            if let Some(git_dir) = git_dir {
+                outside.push(git_dir.join("info").join("exclude"));
+            }
+        }
+        if use_gitignore && sources.global_excludes {
+            outside.extend(gitconfig_excludes_path());
+        }
+        for file in outside {
+            self.fingerprint.push(Stamp::read(&file, &root));
+            if !file.is_file() {
+                continue;
+            }
+            if let Some(matcher) = self.build_matcher(&base, &file) {
+                self.fallback.push(matcher);
+            }
+        }
+    }
+
+    /// Reads `.gitignore` in each directory above the root up to the work tree; their patterns
+    /// apply to everything under the root, so they are stamped with the root as their scope.
+    fn load_parent_gitignores(&mut self) {
+        let root = self.root.clone();
+        let parents: Vec<PathBuf> = root
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.work_tree))
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in parents {
+            let file = dir.join(".gitignore");
+            self.fingerprint.push(Stamp::read(&file, &root));
+            if !file.is_file() {
+                continue;
+            }
+            if let Some(matcher) = self.build_matcher(&dir, &file) {
+                self.by_dir.entry(dir).or_default()[GITIGNORE] = Some(matcher);
+            }
+        }
+    }
+
+    fn build_matcher(&mut self, dir: &Path, file: &Path) -> Option<Gitignore> {
+        let mut builder = GitignoreBuilder::new(dir);
+        if let Some(error) = builder.add(file) {
+            self.warnings
+                .push(format!("Partial ignore file parse error: {error}"));
+        }
+        match builder.build() {
+            Ok(matcher) => Some(matcher),
+            Err(error) => {
+                self.warnings
+                    .push(format!("Failed to build ignore matcher: {error}"));
+                None
+            }
+        }
+    }
+
+    /// Matches `path`, an absolute path under the root, the way `git check-ignore` would. An
+    /// ignored parent directory is reported as the match for everything below it.
+    pub fn matched(&self, path: &Path, is_dir: bool) -> Match<&Glob> {
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return Match::None;
+        };
+        let mut current = self.root.clone();
+        let mut components = rel.components().peekable();
+        while let Some(component) = components.next() {
+            current.push(component);
+            let is_last = components.peek().is_none();
+            let matched = self.matched_level(&current, is_dir || !is_last);
+            if is_last || matched.is_ignore() {
+                return matched;
+            }
+        }
+        Match::None
+    }
+
+    /// Matches one path without looking at its parents.
+    fn matched_level(&self, path: &Path, is_dir: bool) -> Match<&Glob> {
+        let dirs: Vec<&Path> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.work_tree))
+            .collect();
+        for kind in (0..KINDS).rev() {
+            for dir in &dirs {
+                let Some(matcher) = self.by_dir.get(*dir).and_then(|m| m[kind].as_ref()) else {
+                    continue;
+                };
+                let matched = matcher.matched(path, is_dir);
+                if !matched.is_none() {
+                    return matched;
+                }
+            }
+        }
+        self.fallback
+            .iter()
+            .map(|matcher| matcher.matched(path, is_dir))
+            .find(|matched| !matched.is_none())
+            .unwrap_or(Match::None)
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Re-stats the directories and ignore files seen by `load`; false once any of them
+    /// changed, appeared or disappeared.
+    pub fn is_current(&self) -> bool {
+        self.fingerprint.iter().all(Stamp::is_current)
+    }
+
+    /// `is_current` for matching at and directly below `path` only: re-stats the directories
+    /// from the root down to `path`, their ignore files and the ones outside the tree, and
+    /// leaves the rest of the tree alone.
+    pub fn is_current_at(&self, path: &Path) -> bool {
+        self.fingerprint
+            .iter()
+            .filter(|stamp| path.starts_with(&stamp.scope))
+            .all(Stamp::is_current)
+    }
+}
+
+/// Walks up from `root` to the enclosing work tree and returns it with the git directory that
+/// holds `info/exclude`. A `.git` file (worktrees, submodules) points at the git directory, and
+/// a linked worktree shares `info/exclude` with its main repository through `commondir`.
+fn find_repo_root(root: &Path) -> Option<(PathBuf, PathBuf)> {
+    root.ancestors().find_map(|dir| {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some((dir.to_path_buf(), dot_git));
+        }
+        let text = fs::read_to_string(&dot_git).ok()?;
+        let git_dir = dir.join(text.strip_prefix("gitdir:")?.trim());
+        let common_dir = fs::read_to_string(git_dir.join("commondir"))
+            .map(|common| git_dir.join(common.trim()))
+            .unwrap_or(git_dir);
+        Some((dir.to_path_buf(), common_dir))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    use tempfile::tempdir;
+
+    use crate::models::IgnoreSources;
+
+    use super::GitignoreSet;
+
+    struct Case {
+        name: &'static str,
+        ignore_files: &'static [(&'static str, &'static str)],
+        /// Paths ending in `/` are created as directories. Expectations were recorded with
+        /// `git check-ignore --no-index` (git 2.39).
+        expected: &'static [(&'static str, bool)],
+    }
+
+    const CORPUS: &[Case] = &[
+        Case {
+            name: "nested anchor is relative to its directory",
+            ignore_files: &[("packages/a/.gitignore", "/build\n")],
+            expected: &[
+                ("packages/a/build/out.js", true),
+                ("build/out.js", false),
+                ("packages/a/src/build/out.js", false),
+            ],
+        },
+        Case {
+            name: "nested negation overrides parent pattern",
+            ignore_files: &[
+                (".gitignore", "*.log\n"),
+                ("logs/.gitignore", "!keep.log\n"),
+            ],
+            expected: &[
+                ("logs/keep.log", false),
+                ("logs/debug.log", true),
+                ("keep.log", true),
+            ],
+        },
+        Case {
+            name: "excluded directory cannot be re-included",
+            ignore_files: &[(".gitignore", "vendor/\n!vendor/keep.txt\n")],
+            expected: &[("vendor/keep.txt", true), ("vendor/", true)],
+        },
+        Case {
+            name: "directory contents pattern allows re-include",
+            ignore_files: &[(".gitignore", "dist/*\n!dist/keep.txt\n")],
+            expected: &[("dist/keep.txt", false), ("dist/app.js", true)],
+        },
+        Case {
+            name: "deeper file wins over shallower",
+            ignore_files: &[
+                (".gitignore", "*.tmp\n"),
+                ("a/.gitignore", "!*.tmp\n"),
+                ("a/b/.gitignore", "*.tmp\n"),
+            ],
+            expected: &[("x.tmp", true), ("a/x.tmp", false), ("a/b/x.tmp", true)],
+        },
+        Case {
+            name: "middle slash anchors to its directory",
+            ignore_files: &[("src/.gitignore", "gen/*.rs\n")],
+            expected: &[
+                ("src/gen/api.rs", true),
+                ("src/lib/gen/api.rs", false),
+                ("gen/api.rs", false),
+            ],
+        },
+        Case {
+            name: "directory-only pattern skips files",
+            ignore_files: &[(".gitignore", "out/\n")],
+            expected: &[
+                ("out", false),
+                ("nested/out/", true),
+                ("nested/out/log.txt", true),
+            ],
+        },
+        Case {
+            name: "double star stays inside its directory",
+            ignore_files: &[("a/.gitignore", "**/cache\n")],
+            expected: &[
+                ("a/b/cache/f.bin", true),
+                ("a/cache", true),
+                ("cache/f.bin", false),
+            ],
+        },
+        Case {
+            name: "last matching line wins",
+            ignore_files: &[(".gitignore", "!notes.txt\n*.txt\n")],
+            expected: &[("notes.txt", true)],
+        },
+        Case {
+            name: "unanchored name matches at any depth below",
+            ignore_files: &[("a/.gitignore", "b\n")],
+            expected: &[("a/x/b/f.txt", true), ("b/f.txt", false)],
+        },
+        Case {
+            name: "negated directory reopens only that directory",
+            ignore_files: &[(".gitignore", "/*\n!/src/\n")],
+            expected: &[
+                ("src/main.rs", false),
+                ("docs/readme.md", true),
+                ("top.txt", true),
+            ],
+        },
+        Case {
+            name: "escaped hash and bang",
+            ignore_files: &[(".gitignore", "\\#notes\n\\!important\n")],
+            expected: &[("#notes", true), ("!important", true)],
+        },
+    ];
+
+    fn materialize(root: &Path, case: &Case) {
+        for (path, content) in case.ignore_files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        for (path, _) in case.expected {
+            let path = root.join(path);
+            if path.to_string_lossy().ends_with('/') {
+                fs::create_dir_all(path).unwrap();
+            } else {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, "x").unwrap();
+            }
+        }
+    }
+
+    fn local_sources() -> IgnoreSources {
+        IgnoreSources {
+            global_excludes: false,
+            ..IgnoreSources::default()
+        }
+    }
+
+    #[test]
+    fn reads_every_enabled_ignore_file() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root_path.join(".git/info")).unwrap();
+        fs::write(root_path.join(".git/info/exclude"), "*.exclude\n").unwrap();
+        fs::write(root_path.join(".gitignore"), "*.gitignore\n").unwrap();
+        fs::write(root_path.join(".ignore"), "*.ignore\n").unwrap();
+        fs::write(root_path.join(".rgignore"), "*.rgignore\n").unwrap();
+        fs::write(root_path.join(".codebasetotxtignore"), "*.project\n").unwrap();
+        let ignored =
+            |set: &GitignoreSet, name: &str| set.matched(&root_path.join(name), false).is_ignore();
+
+        let all = GitignoreSet::load(&root_path, true, local_sources());
+        for name in [
+            "a.exclude",
+            "a.gitignore",
+            "a.ignore",
+            "a.rgignore",
+            "a.project",
+        ] {
+            assert!(ignored(&all, name), "{name} should be ignored");
+        }
+
+        let project_only = GitignoreSet::load(
+            &root_path,
+            false,
+            IgnoreSources {
+                dot_ignore: false,
+                rgignore: false,
+                git_info_exclude: false,
+                global_excludes: false,
+            },
+        );
+        for name in ["a.exclude", "a.gitignore", "a.ignore", "a.rgignore"] {
+            assert!(
+                !ignored(&project_only, name),
+                "{name} should not be ignored"
+            );
+        }
+        assert!(ignored(&project_only, "a.project"));
+
+        let without_git = GitignoreSet::load(&root_path, false, local_sources());
+        assert!(!ignored(&without_git, "a.exclude"));
+        assert!(!ignored(&without_git, "a.gitignore"));
+        assert!(ignored(&without_git, "a.ignore"));
+    }
+
+    #[test]
+    fn load_skips_ignored_and_rejected_directories() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        for dir in ["build/nested", "vendor/lib", "src"] {
+            fs::create_dir_all(root_path.join(dir)).unwrap();
+        }
+        fs::write(root_path.join(".gitignore"), "build/\n").unwrap();
+        fs::write(root_path.join("build/nested/.gitignore"), "*.rs\n").unwrap();
+        fs::write(root_path.join("vendor/lib/.gitignore"), "*.rs\n").unwrap();
+        fs::write(root_path.join("src/.gitignore"), "*.tmp\n").unwrap();
+
+        let set = GitignoreSet::load_pruned(&root_path, true, local_sources(), |rel_path| {
+            rel_path == "vendor"
+        });
+        assert!(set.matched(&root_path.join("src/a.tmp"), false).is_ignore());
+        assert!(!set
+            .matched(&root_path.join("vendor/lib/a.rs"), false)
+            .is_ignore());
+
+        fs::write(root_path.join("build/nested/new.rs"), "").unwrap();
+        fs::write(root_path.join("vendor/lib/.ignore"), "*\n").unwrap();
+        assert!(set.is_current());
+    }
+
+    #[test]
+    fn reads_gitignore_files_above_a_root_inside_the_work_tree() {
+        let repo = tempdir().unwrap();
+        let repo_path = repo.path().canonicalize().unwrap();
+        fs::create_dir_all(repo_path.join(".git")).unwrap();
+        fs::create_dir_all(repo_path.join("app/build")).unwrap();
+        fs::write(repo_path.join(".gitignore"), "*.log\n/app/build/\n").unwrap();
+        let root_path = repo_path.join("app");
+
+        let set = GitignoreSet::load(&root_path, true, local_sources());
+        assert!(set.matched(&root_path.join("debug.log"), false).is_ignore());
+        assert!(set
+            .matched(&root_path.join("build/out.js"), false)
+            .is_ignore());
+        assert!(!set.matched(&root_path.join("main.rs"), false).is_ignore());
+
+        let without_git = GitignoreSet::load(&root_path, false, local_sources());
+        assert!(!without_git
+            .matched(&root_path.join("debug.log"), false)
+            .is_ignore());
+
+        fs::write(repo_path.join(".gitignore"), "*.tmp\n").unwrap();
+        assert!(!set.is_current());
+        assert!(!set.is_current_at(&root_path.join("build")));
+    }
+
+    #[test]
+    fn project_ignore_file_overrides_gitignore() {
+        let root = tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        fs::write(root_path.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root_path.join(".codebasetotxtignore"), "!keep.log\n").unwrap();
+
+        let set = GitignoreSet::load(&root_path, true, local_sources());
+
+        assert!(set.matched(&root_path.join("debug.log"), false).is_ignore());
+        assert!(set
+            .matched(&root_path.join("keep.log"), false)
+            .is_whitelist());
+    }
+
+    #[test]
+    fn matches_git_check_ignore_corpus() {
+        for case in CORPUS {
+            let root = tempdir().unwrap();
+            let root_path = root.path().canonicalize().unwrap();
+            materialize(&root_path, case);
+            let set = GitignoreSet::load(&root_path, true, local_sources());
+
+            for (path, ignored) in case.expected {
+                let is_dir = path.ends_with('/');
+                let abs_path = root_path.join(path.trim_end_matches('/'));
+                assert_eq!(
+                    set.matched(&abs_path, is_dir).is_ignore(),
+                    *ignored,
+                    "{}: {path}",
+                    case.name
+                );
+            }
+        }
+    }
+
+    /// Re-checks the recorded corpus against the installed git, when there is one.
+    #[test]
+    fn corpus_agrees_with_installed_git() {
+        let git_available = Command::new("git")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !git_available {
+            return;
+        }
+        for case in CORPUS {
+            let root = tempdir().unwrap();
+            materialize(root.path(), case);
+            let init = Command::new("git")
+                .args(["init", "-q"])
+                .current_dir(root.path())
+                .status()
+                .unwrap();
+            assert!(init.success());
+
+            for (path, ignored) in case.expected {
+                let status = Command::new("git")
+                    .args([
+                        "-c",
+                        "core.excludesFile=",
+                        "check-ignore",
+                        "-q",
+                        "--no-index",
+                        path,
+                    ])
+                    .current_dir(root.path())
+                    .status()
+                    .unwrap();
+                assert_eq!(status.success(), *ignored, "{}: {path}", case.name);
+            }
+        }
+    }
+}
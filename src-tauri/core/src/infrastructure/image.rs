@@ -0,0 +1,222 @@
+use std::io::{self, Read};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Dimensions and color model of a raster image, read from its header only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    /// e.g. "RGBA, 8-bit", "YCbCr, 8-bit, progressive", "indexed, 256 colors".
+    pub color: String,
+}
+
+/// Parses PNG, JPEG, GIF and WebP headers from the start of `reader`; `None` for anything else
+/// or a header too short or malformed to read.
+pub fn read_info(reader: &mut impl Read) -> Option<ImageInfo> {
+    let mut head = [0u8; 30];
+    let len = read_up_to(reader, &mut head).ok()?;
+    let head = &head[..len];
+    if head.starts_with(PNG_SIGNATURE) {
+        png_info(head)
+    } else if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        gif_info(head)
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP") {
+        webp_info(head)
+    } else if head.starts_with(&[0xff, 0xd8]) {
+        jpeg_info(&head[2..], reader).ok().flatten()
+    } else {
+        None
+    }
+}
+
+fn png_info(head: &[u8]) -> Option<ImageInfo> {
+    if head.get(12..16)? != b"IHDR" || head.len() < 26 {
+        return None;
+    }
+    let color = match head[25] {
+        0 => "grayscale",
+        2 => "RGB",
+        3 => "indexed",
+        4 => "grayscale + alpha",
+        6 => "RGBA",
+        _ => return None,
+    };
+    Some(ImageInfo {
+        width: u32::from_be_bytes(head[16..20].try_into().ok()?),
+        height: u32::from_be_bytes(head[20..24].try_into().ok()?),
+        color: format!("{color}, {}-bit", head[24]),
+    })
+}
+
+fn gif_info(head: &[u8]) -> Option<ImageInfo> {
+    let flags = *head.get(10)?;
+    let color = if flags & 0x80 != 0 {
+        format!("indexed, {} colors", 2u32 << (flags & 0x07))
+    } else {
+        "indexed".to_string()
+    };
+    Some(ImageInfo {
+        width: u16::from_le_bytes([head[6], head[7]]).into(),
+        height: u16::from_le_bytes([head[8], head[9]]).into(),
+        color,
+    })
+}
+
+fn webp_info(head: &[u8]) -> Option<ImageInfo> {
+    if head.len() < 30 {
+        return None;
+    }
+    let data = &head[20..];
+    let le24 = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+    match &head[12..16] {
+        b"VP8 " if data[3..6] == [0x9d, 0x01, 0x2a] => Some(ImageInfo {
+            width: u32::from(u16::from_le_bytes([data[6], data[7]]) & 0x3fff),
+            height: u32::from(u16::from_le_bytes([data[8], data[9]]) & 0x3fff),
+            color: "YUV 4:2:0, lossy".to_string(),
+        }),
+        b"VP8L" if data[0] == 0x2f => {
+            let bits = u32::from_le_bytes(data[1..5].try_into().ok()?);
+            let color = if bits & (1 << 28) != 0 { "RGBA" } else { "RGB" };
+            Some(ImageInfo {
+                width: (bits & 0x3fff) + 1,
+                height: ((bits >> 14) & 0x3fff) + 1,
+                color: format!("{color}, lossless"),
+            })
+        }
+        b"VP8X" => {
+            let flags = data[0];
+            let mut color = if flags & 0x10 != 0 { "RGBA" } else { "RGB" }.to_string();
+            if flags & 0x02 != 0 {
+                color.push_str(", animated");
+            }
+            Some(ImageInfo {
+                width: le24(&data[4..7]) + 1,
+                height: le24(&data[7..10]) + 1,
+                color,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Walks the marker segments after SOI until the first start-of-frame; `head` holds the bytes
+/// already read past SOI. EXIF and ICC segments can run to tens of kilobytes before it.
+fn jpeg_info(head: &[u8], reader: &mut impl Read) -> io::Result<Option<ImageInfo>> {
+    let mut reader = head.chain(reader);
+    let mut byte = [0u8; 1];
+    loop {
+        reader.read_exact(&mut byte)?;
+        if byte[0] != 0xff {
+            return Ok(None);
+        }
+        let mut marker = 0xff;
+        while marker == 0xff {
+            reader.read_exact(&mut byte)?;
+            marker = byte[0];
+        }
+        match marker {
+            0x01 | 0xd0..=0xd7 => continue,
+            0xd9 | 0xda => return Ok(None),
+            _ => {}
+        }
+        let mut length = [0u8; 2];
+        reader.read_exact(&mut length)?;
+        let length = u64::from(u16::from_be_bytes(length)).saturating_sub(2);
+        let is_frame = matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc);
+        if !is_frame {
+            io::copy(&mut (&mut reader).take(length), &mut io::sink())?;
+            continue;
+        }
+        let mut frame = [0u8; 6];
+        reader.read_exact(&mut frame)?;
+        let components = match frame[5] {
+            1 => "grayscale".to_string(),
+            3 => "YCbCr".to_string(),
+            4 => "CMYK".to_string(),
+            count => format!("{count} components"),
+        };
+        let mut color = format!("{components}, {}-bit", frame[0]);
+        if matches!(marker, 0xc2 | 0xc6 | 0xca | 0xce) {
+            color.push_str(", progressive");
+        }
+        return Ok(Some(ImageInfo {
+            width: u16::from_be_bytes([frame[3], frame[4]]).into(),
+            height: u16::from_be_bytes([frame[1], frame[2]]).into(),
+            color,
+        }));
+    }
+}
+
+fn read_up_to(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..])? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_info, ImageInfo};
+
+    fn info(width: u32, height: u32, color: &str) -> Option<ImageInfo> {
+        Some(ImageInfo {
+            width,
+            height,
+            color: color.to_string(),
+        })
+    }
+
+    #[test]
+    fn reads_dimensions_and_color_from_headers() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0\x08\x06\0\0\0\0";
+        assert_eq!(read_info(&mut &png[..]), info(640, 480, "RGBA, 8-bit"));
+
+        let gif = b"GIF89a\x10\0\x20\0\xf7\0\0";
+        assert_eq!(
+            read_info(&mut &gif[..]),
+            info(16, 32, "indexed, 256 colors")
+        );
+
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 0x40];
+        jpeg.extend([0u8; 62]);
+        jpeg.extend([0xff, 0xc2, 0x00, 0x11, 0x08, 0x02, 0x58, 0x03, 0x20, 0x03]);
+        assert_eq!(
+            read_info(&mut jpeg.as_slice()),
+            info(800, 600, "YCbCr, 8-bit, progressive")
+        );
+
+        let mut lossy = b"RIFF\0\0\0\0WEBPVP8 \0\0\0\0\0\0\0\x9d\x01\x2a".to_vec();
+        lossy.extend([0x90, 0x01, 0x2c, 0x01]);
+        assert_eq!(
+            read_info(&mut lossy.as_slice()),
+            info(400, 300, "YUV 4:2:0, lossy")
+        );
+
+        let mut lossless = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2f".to_vec();
+        let bits: u32 = 99 | (49 << 14) | (1 << 28);
+        lossless.extend(bits.to_le_bytes());
+        lossless.extend([0u8; 5]);
+        assert_eq!(
+            read_info(&mut lossless.as_slice()),
+            info(100, 50, "RGBA, lossless")
+        );
+
+        let mut extended = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\x12\0\0\0".to_vec();
+        extended.extend([0xff, 0x03, 0x00, 0xff, 0x01, 0x00]);
+        assert_eq!(
+            read_info(&mut extended.as_slice()),
+            info(1024, 512, "RGBA, animated")
+        );
+
+        assert_eq!(read_info(&mut &b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"[..]), None);
+        assert_eq!(
+            read_info(&mut &b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"[..]),
+            None
+        );
+    }
+}
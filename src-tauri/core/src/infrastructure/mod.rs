@@ -0,0 +1,10 @@
+pub mod binary;
+pub mod encoding;
+pub mod errors;
+pub mod fs_scan;
+pub mod gitignore;
+pub mod image;
+pub mod pathing;
+pub mod sorting;
+pub mod staged_output;
+pub mod tokenizer;
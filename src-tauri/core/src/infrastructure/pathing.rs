@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::errors::{read_error, AppError, AppResult};
+
+pub fn canonicalize_dir(path: &str) -> AppResult<PathBuf> {
+    let raw = path.trim();
+    if raw.is_empty() {
+        return Err(AppError::RootRequired);
+    }
+    let canonical = fs::canonicalize(raw).map_err(|e| AppError::RootInvalid {
+        path: raw.to_string(),
+        kind: e.kind(),
+        reason: e.to_string(),
+    })?;
+    if !canonical.is_dir() {
+        return Err(AppError::RootNotDir {
+            path: display_path(&canonical),
+        });
+    }
+    Ok(canonical)
+}
+
+pub fn canonicalize_existing(path: &Path) -> AppResult<PathBuf> {
+    fs::canonicalize(path)
+        .map_err(|e| read_error("Failed to canonicalize path", e).at_path(&display_path(path)))
+}
+
+pub fn ensure_under_root(root: &Path, candidate: &Path) -> AppResult<PathBuf> {
+    let canonical = canonicalize_existing(candidate)?;
+    if canonical.starts_with(root) {
+        return Ok(canonical);
+    }
+    Err(AppError::PathOutsideRoot {
+        path: display_path(candidate),
+    })
+}
+
+pub fn relative_unix_path(root: &Path, abs: &Path) -> AppResult<String> {
+    let rel = abs
+        .strip_prefix(root)
+        .map_err(|_| AppError::PathOutsideRoot {
+            path: display_path(abs),
+        })?;
+    let text = rel
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string();
+    Ok(text)
+}
+
+/// Forward-slash form of `path` for messages and results.
+pub fn display_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+pub fn file_name_or_fallback(path: &Path, fallback: &str) -> String {
+    path.file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::infrastructure::errors::AppError;
+
+    use super::{canonicalize_dir, ensure_under_root};
+
+    #[test]
+    fn canonicalize_dir_rejects_empty_root_path() {
+        let result = canonicalize_dir("   ");
+        assert_eq!(result.unwrap_err(), AppError::RootRequired);
+    }
+
+    #[test]
+    fn canonicalize_dir_rejects_non_existing_root_path() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing");
+        let result = canonicalize_dir(missing.to_string_lossy().as_ref());
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::RootInvalid {
+                kind: std::io::ErrorKind::NotFound,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn ensure_under_root_rejects_outside_path() {
+        let root = tempdir().unwrap();
+        let outside_parent = tempdir().unwrap();
+        let outside = outside_parent.path().join("outside.txt");
+        fs::write(&outside, "x").unwrap();
+
+        let result = ensure_under_root(root.path(), &outside);
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::PathOutsideRoot { .. }
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::path::Path;
+
+pub fn compare_entries(path_a: &Path, is_dir_a: bool, path_b: &Path, is_dir_b: bool) -> Ordering {
+    match (is_dir_a, is_dir_b) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => compare_names(&path_a.to_string_lossy(), &path_b.to_string_lossy()),
+    }
+}
+
+/// Case-insensitive, with an exact comparison breaking ties so the order is total.
+pub fn compare_names(a: &str, b: &str) -> Ordering {
+    let primary = a.to_lowercase().cmp(&b.to_lowercase());
+    if primary == Ordering::Equal {
+        a.cmp(b)
+    } else {
+        primary
+    }
+}
+
+/// Orders siblings so a depth-first walk visits files in `compare_names` order of their relative
+/// paths: a directory compares as its name followed by `/`, which puts `src-gen/` and `src.rs`
+/// before `src/` just as the full paths sort. Sibling directories differing only in case are
+/// walked one after the other rather than interleaved.
+pub fn compare_path_order(
+    name_a: &OsStr,
+    is_dir_a: bool,
+    name_b: &OsStr,
+    is_dir_b: bool,
+) -> Ordering {
+    let key = |name: &OsStr, is_dir: bool| {
+        let mut key = name.to_string_lossy().into_owned();
+        if is_dir {
+            key.push('/');
+        }
+        key
+    };
+    compare_names(&key(name_a, is_dir_a), &key(name_b, is_dir_b))
+}
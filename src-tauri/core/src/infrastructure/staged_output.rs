@@ -0,0 +1,175 @@
+use std::ffi::OsString;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tempfile::{Builder, NamedTempFile};
+
+use crate::infrastructure::errors::{write_error, AppError, AppResult};
+use crate::infrastructure::pathing::display_path;
+use crate::models::OverwritePolicy;
+
+const MAX_SUFFIX_ATTEMPTS: u32 = 9999;
+
+/// Export output written to a temporary sibling of the target and renamed into place on
+/// `commit`. Dropping it without committing removes the temporary file.
+pub struct StagedOutput {
+    temp: NamedTempFile,
+    target: PathBuf,
+}
+
+impl StagedOutput {
+    pub fn create(target: &Path) -> AppResult<Self> {
+        let parent = target
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let mut prefix = OsString::from(".");
+        prefix.push(target.file_name().unwrap_or_default());
+        prefix.push(".");
+
+        let mut builder = Builder::new();
+        builder.prefix(&prefix).suffix(".tmp");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o666));
+        }
+        let temp = builder.tempfile_in(parent).map_err(|e| {
+            write_error("Failed to create temporary output file", e).at_path(&display_path(parent))
+        })?;
+
+        if let Ok(existing) = target.metadata() {
+            let _ = temp.as_file().set_permissions(existing.permissions());
+        }
+
+        Ok(Self {
+            temp,
+            target: target.to_path_buf(),
+        })
+    }
+
+    pub fn file_mut(&mut self) -> &mut File {
+        self.temp.as_file_mut()
+    }
+
+    /// Moves the finished output into place and returns the path it ended up at, which differs
+    /// from the requested target only under `OverwritePolicy::AutoSuffix`.
+    pub fn commit(self, policy: OverwritePolicy) -> AppResult<PathBuf> {
+        let target_label = display_path(&self.target);
+        self.temp
+            .as_file()
+            .sync_all()
+            .map_err(|e| write_error("Failed to flush output file", e).at_path(&target_label))?;
+
+        match policy {
+            OverwritePolicy::Overwrite => {
+                self.temp.persist(&self.target).map_err(|e| {
+                    write_error("Failed to move output file into place", e.error)
+                        .at_path(&target_label)
+                })?;
+                Ok(self.target)
+            }
+            OverwritePolicy::FailIfExists => {
+                self.temp
+                    .persist_noclobber(&self.target)
+                    .map_err(|e| match e.error.kind() {
+                        io::ErrorKind::AlreadyExists => AppError::OutputExists {
+                            path: target_label.clone(),
+                        },
+                        _ => write_error("Failed to move output file into place", e.error)
+                            .at_path(&target_label),
+                    })?;
+                Ok(self.target)
+            }
+            OverwritePolicy::AutoSuffix => {
+                let mut temp = self.temp;
+                for attempt in 0..=MAX_SUFFIX_ATTEMPTS {
+                    let candidate = suffixed_path(&self.target, attempt);
+                    match temp.persist_noclobber(&candidate) {
+                        Ok(_) => return Ok(candidate),
+                        Err(e) if e.error.kind() == io::ErrorKind::AlreadyExists => temp = e.file,
+                        Err(e) => {
+                            return Err(write_error(
+                                "Failed to move output file into place",
+                                e.error,
+                            )
+                            .at_path(&display_path(&candidate)))
+                        }
+                    }
+                }
+                Err(AppError::OutputExists { path: target_label })
+            }
+        }
+    }
+}
+
+/// `export.txt` -> `export-1.txt`, `export-2.txt`, ...; attempt 0 is the path itself.
+fn suffixed_path(target: &Path, attempt: u32) -> PathBuf {
+    if attempt == 0 {
+        return target.to_path_buf();
+    }
+    let stem = target.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match target.extension() {
+        Some(ext) => format!("{stem}-{attempt}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{attempt}"),
+    };
+    target.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+
+    use tempfile::tempdir;
+
+    use crate::infrastructure::errors::AppError;
+    use crate::models::OverwritePolicy;
+
+    use super::StagedOutput;
+
+    fn stage(target: &std::path::Path, content: &str) -> StagedOutput {
+        let mut staged = StagedOutput::create(target).unwrap();
+        staged.file_mut().write_all(content.as_bytes()).unwrap();
+        staged
+    }
+
+    #[test]
+    fn dropped_output_leaves_no_files_behind() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("export.txt");
+        drop(stage(&target, "partial"));
+
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn fail_if_exists_keeps_existing_file() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("export.txt");
+        fs::write(&target, "old").unwrap();
+
+        let result = stage(&target, "new").commit(OverwritePolicy::FailIfExists);
+
+        assert!(matches!(result.unwrap_err(), AppError::OutputExists { .. }));
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn auto_suffix_picks_the_first_free_name() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("export.txt");
+        fs::write(&target, "old").unwrap();
+        fs::write(dir.path().join("export-1.txt"), "older").unwrap();
+
+        let written = stage(&target, "new")
+            .commit(OverwritePolicy::AutoSuffix)
+            .unwrap();
+
+        assert_eq!(written, dir.path().join("export-2.txt"));
+        assert_eq!(fs::read_to_string(&written).unwrap(), "new");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::OnceLock;
+
+use tiktoken_rs::CoreBPE;
+
+use crate::models::TokenizerKind;
+
+static CL100K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+static O200K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+
+pub struct Tokenizer {
+    bpe: Option<&'static CoreBPE>,
+    fell_back: bool,
+}
+
+impl Tokenizer {
+    pub fn new(kind: TokenizerKind) -> Self {
+        let bpe = match kind {
+            TokenizerKind::Cl100k => CL100K
+                .get_or_init(|| tiktoken_rs::cl100k_base().ok())
+                .as_ref(),
+            TokenizerKind::O200k => O200K
+                .get_or_init(|| tiktoken_rs::o200k_base().ok())
+                .as_ref(),
+            TokenizerKind::Heuristic => None,
+        };
+        Self {
+            bpe,
+            fell_back: bpe.is_none() && !matches!(kind, TokenizerKind::Heuristic),
+        }
+    }
+
+    /// True when a BPE vocabulary was requested but could not be loaded.
+    pub fn fell_back(&self) -> bool {
+        self.fell_back
+    }
+
+    pub fn count(&self, text: &str) -> u64 {
+        match self.bpe {
+            Some(bpe) => bpe.encode_ordinary(text).len() as u64,
+            None => heuristic_count(text),
+        }
+    }
+}
+
+/// Approximates BPE output: word runs cost one token per four characters, symbol
+/// runs one per three, non-ASCII characters one each and line breaks one per run.
+fn heuristic_count(text: &str) -> u64 {
+    let mut tokens = 0u64;
+    let mut word_len = 0u64;
+    let mut symbol_len = 0u64;
+    let mut in_break = false;
+
+    for ch in text.chars() {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            tokens += symbol_len.div_ceil(3);
+            symbol_len = 0;
+            word_len += 1;
+            in_break = false;
+            continue;
+        }
+        tokens += word_len.div_ceil(4);
+        word_len = 0;
+
+        if ch.is_whitespace() {
+            tokens += symbol_len.div_ceil(3);
+            symbol_len = 0;
+            if (ch == '\n' || ch == '\r') && !in_break {
+                tokens += 1;
+                in_break = true;
+            }
+        } else if ch.is_ascii() {
+            symbol_len += 1;
+            in_break = false;
+        } else {
+            tokens += symbol_len.div_ceil(3) + 1;
+            symbol_len = 0;
+            in_break = false;
+        }
+    }
+
+    tokens + word_len.div_ceil(4) + symbol_len.div_ceil(3)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::TokenizerKind;
+
+    use super::Tokenizer;
+
+    #[test]
+    fn bpe_tokenizers_count_known_text() {
+        let cl100k = Tokenizer::new(TokenizerKind::Cl100k);
+        let o200k = Tokenizer::new(TokenizerKind::O200k);
+        assert!(!cl100k.fell_back());
+        assert_eq!(cl100k.count("hello world"), 2);
+        assert_eq!(o200k.count("hello world"), 2);
+        assert_eq!(cl100k.count(""), 0);
+    }
+
+    #[test]
+    fn heuristic_stays_close_to_bpe_for_source_code() {
+        let source = "fn main() {\n    let total = items.iter().map(|item| item.size).sum::<u64>();\n    println!(\"{total}\");\n}\n";
+        let exact = Tokenizer::new(TokenizerKind::Cl100k).count(source) as f64;
+        let approx = Tokenizer::new(TokenizerKind::Heuristic).count(source) as f64;
+        assert!(
+            (approx - exact).abs() / exact < 0.35,
+            "exact={exact} approx={approx}"
+        );
+    }
+}
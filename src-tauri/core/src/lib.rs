@@ -0,0 +1,20 @@
+//! Scanning, rule evaluation and export engine behind Codebase to Txt.
+//!
+//! The crate has no Tauri or webview dependencies. The functions and types re-exported
+//! here are the supported entry points; the desktop app and the CLI use nothing else.
+
+mod application;
+mod domain;
+mod infrastructure;
+pub mod models;
+
+pub use application::exporter::{
+    evaluate_selection, export_to_writer, preview_export, run_export, run_export_observed,
+};
+pub use application::progress::{ExportObserver, Unobserved};
+pub use application::scanner::{explain_path, scan_children, scan_root};
+pub use application::selection::{
+    collect_selected_files, SelectedFile, SelectionRun, SelectionWalk,
+};
+pub use domain::rules::{Decision, RuleEngine};
+pub use infrastructure::errors::{AppError, AppResult};
@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportConfig {
+    pub root_path: String,
+    /// Reads `.gitignore` files, and gates `.git/info/exclude` and the global excludes file:
+    /// with it off, those are skipped whatever `ignore_sources` says.
+    pub use_gitignore: bool,
+    pub include_globs: Vec<String>,
+    pub exclude_globs: Vec<String>,
+    pub include_extensions: Vec<String>,
+    pub exclude_extensions: Vec<String>,
+    #[serde(default)]
+    pub structure_only: bool,
+    #[serde(rename = "maxFileSizeKB", alias = "maxFileSizeKb")]
+    pub max_file_size_kb: u64,
+    pub large_file_strategy: LargeFileStrategy,
+    /// Files with more lines than this are large too, whatever their size.
+    #[serde(default)]
+    pub max_file_lines: Option<u64>,
+    /// Cut head-only truncation (large files and token budget cuts) back to the last whole line
+    /// instead of at the byte limit.
+    #[serde(default)]
+    pub truncate_at_line_boundary: bool,
+    /// Checked in order; the first glob matching a file's path replaces the limits above for it.
+    #[serde(default)]
+    pub size_overrides: Vec<SizeOverride>,
+    #[serde(default)]
+    pub binary_policy: BinaryPolicy,
+    /// Largest file `BinaryPolicy::Hex` and `BinaryPolicy::Base64` dump; bigger ones get the
+    /// metadata stub only.
+    #[serde(rename = "binaryDumpMaxKB", default = "default_binary_dump_max_kb")]
+    pub binary_dump_max_kb: u64,
+    pub manual_selections: BTreeMap<String, ManualSelectionState>,
+    pub output_format: OutputFormat,
+    #[serde(default)]
+    pub tokenizer: TokenizerKind,
+    #[serde(default)]
+    pub token_budget: Option<u64>,
+    #[serde(default)]
+    pub priority_globs: Vec<String>,
+    #[serde(default)]
+    pub overwrite_policy: OverwritePolicy,
+    #[serde(default)]
+    pub ignore_sources: IgnoreSources,
+    /// Excluded ahead of every rule except a manual include at or below the matched path.
+    #[serde(default = "default_hard_excludes")]
+    pub hard_excludes: Vec<String>,
+    #[serde(default)]
+    pub secret_action: SecretAction,
+    /// Checked in order; the first glob matching a file's path decides its encoding.
+    #[serde(default)]
+    pub encoding_overrides: Vec<EncodingOverride>,
+    #[serde(default)]
+    pub line_endings: LineEndingMode,
+}
+
+/// Size limits for files matching `glob`; unset fields keep the global setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeOverride {
+    pub glob: String,
+    #[serde(default, rename = "maxFileSizeKB", alias = "maxFileSizeKb")]
+    pub max_file_size_kb: Option<u64>,
+    #[serde(default)]
+    pub max_file_lines: Option<u64>,
+    #[serde(default)]
+    pub large_file_strategy: Option<LargeFileStrategy>,
+}
+
+/// Reads files matching `glob` as `encoding` (a WHATWG label such as `shift_jis` or
+/// `utf-16le`) instead of detecting it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncodingOverride {
+    pub glob: String,
+    pub encoding: String,
+}
+
+/// Dependency, build output, tool state and lockfile paths that are rarely worth exporting.
+/// Entries without a `/` match a file or directory name at any depth.
+pub const DEFAULT_HARD_EXCLUDES: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    ".venv",
+    "__pycache__",
+    ".idea",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "Cargo.lock",
+    "poetry.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "composer.lock",
+];
+
+pub fn default_hard_excludes() -> Vec<String> {
+    DEFAULT_HARD_EXCLUDES
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect()
+}
+
+/// Ignore files read besides `.gitignore`, which `use_gitignore` controls. The git-only sources
+/// are read only when `use_gitignore` is on too. The project's `.codebasetotxtignore` is always
+/// read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IgnoreSources {
+    /// `.ignore` files, as read by ripgrep and fd.
+    pub dot_ignore: bool,
+    /// `.rgignore` files.
+    pub rgignore: bool,
+    /// The repository's `.git/info/exclude`.
+    pub git_info_exclude: bool,
+    /// The user's `core.excludesFile`, or git's default global ignore file.
+    pub global_excludes: bool,
+}
+
+impl Default for IgnoreSources {
+    fn default() -> Self {
+        Self {
+            dot_ignore: true,
+            rgignore: true,
+            git_info_exclude: true,
+            global_excludes: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LargeFileStrategy {
+    /// Keep the head of the file.
+    Truncate,
+    /// Keep the first and last half of the limit, with a marker line counting the lines left out
+    /// between them. The whole file is read to count them.
+    HeadTail,
+    Skip,
+    /// Export large files whole; mostly useful in a size override. Files over 32 MB are still
+    /// cut to their head, since each one is read into memory.
+    Keep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManualSelectionState {
+    Include,
+    Exclude,
+    Inherit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    Txt,
+    Md,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    #[default]
+    Cl100k,
+    O200k,
+    Heuristic,
+}
+
+/// What the export does with a file whose content looks like it holds credentials.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretAction {
+    Off,
+    #[default]
+    Warn,
+    Redact,
+    Skip,
+}
+
+/// How exported file contents treat CRLF and CR line endings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEndingMode {
+    /// Convert every line ending to LF.
+    #[default]
+    Normalize,
+    /// Convert to LF and note each file that had other line endings, with its original style.
+    Annotate,
+    /// Write line endings exactly as stored.
+    Preserve,
+}
+
+/// What the export does with a file detected as binary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryPolicy {
+    /// Raster images still get the `Metadata` stub with their dimensions.
+    #[default]
+    Skip,
+    /// Skip the content but show size and MIME type next to the file in the structure.
+    List,
+    /// Write a stub with size, MIME type and SHA-256 instead of the content; hashing reads the
+    /// whole file.
+    Metadata,
+    /// The metadata stub followed by a hex dump of files up to `binaryDumpMaxKB`.
+    Hex,
+    /// The metadata stub followed by base64 of files up to `binaryDumpMaxKB`.
+    Base64,
+}
+
+pub fn default_binary_dump_max_kb() -> u64 {
+    4
+}
+
+/// What `run_export` does when the output file already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwritePolicy {
+    #[default]
+    Overwrite,
+    FailIfExists,
+    AutoSuffix,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNode {
+    pub path: String,
+    pub name: String,
+    pub is_dir: bool,
+    pub children_count: Option<usize>,
+    #[serde(default)]
+    pub included_by_rules: bool,
+    #[serde(default)]
+    pub ignored_by_gitignore: bool,
+    pub children: Vec<TreeNode>,
+}
+
+/// The root of a scan with its first level loaded, plus warnings raised while scanning it,
+/// such as ignore files that failed to parse.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanTree {
+    pub root: TreeNode,
+    pub warnings: Vec<String>,
+}
+
+/// One directory level loaded on demand, plus warnings raised while scanning it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanBatch {
+    pub nodes: Vec<TreeNode>,
+    pub warnings: Vec<String>,
+}
+
+/// The rule that decided whether a path is exported, in the order `RuleEngine` checks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionRule {
+    HardExclude,
+    HardExcludeList,
+    SensitiveFile,
+    ManualInclude,
+    ManualExclude,
+    IncludeGlobMismatch,
+    IncludeExtensionMismatch,
+    IncludeGlob,
+    IncludeExtension,
+    ExcludeExtension,
+    ExcludeGlob,
+    Gitignore,
+    GitignoreNegation,
+    Default,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSource {
+    pub file: String,
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionExplanation {
+    pub path: String,
+    pub is_dir: bool,
+    pub included: bool,
+    pub rule: DecisionRule,
+    /// Glob, extension, manual selection key or gitignore pattern that matched, if any.
+    pub pattern: Option<String>,
+    /// Where a gitignore pattern was read from.
+    pub source: Option<RuleSource>,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionSummary {
+    pub included_files: usize,
+    pub excluded_files: usize,
+    pub warnings: Vec<String>,
+    #[serde(default)]
+    pub budget: Option<BudgetReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    pub token_budget: u64,
+    pub estimated_tokens: u64,
+    pub within_budget: bool,
+    pub cuts: Vec<BudgetCut>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCut {
+    pub path: String,
+    pub action: BudgetAction,
+    pub tokens_before: u64,
+    pub tokens_after: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    Dropped,
+    Truncated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewMeta {
+    pub included_files: usize,
+    pub estimated_bytes: u64,
+    pub estimated_tokens: Option<u64>,
+    #[serde(default)]
+    pub file_tokens: Vec<PathTokenCount>,
+    #[serde(default)]
+    pub directory_tokens: Vec<PathTokenCount>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathTokenCount {
+    pub path: String,
+    pub tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub output_path: String,
+    pub exported_files: usize,
+    pub skipped_files: usize,
+    pub total_bytes_written: u64,
+    pub notes: Vec<String>,
+    /// Exported files with CRLF, CR or mixed line endings or with trailing whitespace, in output
+    /// order; files with only LF endings and no trailing whitespace are left out.
+    pub line_stats: Vec<FileLineStats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEndingStyle {
+    Lf,
+    Crlf,
+    Cr,
+    Mixed,
+}
+
+/// Line endings and trailing whitespace of one exported file as stored, before any
+/// normalization. Counts cover what was read: the head of a file truncated to its head, the whole
+/// file otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileLineStats {
+    pub path: String,
+    /// `None` when the file has no line breaks.
+    pub line_endings: Option<LineEndingStyle>,
+    pub lf_lines: usize,
+    pub crlf_lines: usize,
+    pub cr_lines: usize,
+    pub trailing_whitespace_lines: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportPhase {
+    /// Collecting the whole selection up front, which only a token budget needs; `total_files`
+    /// is not known yet.
+    Selecting,
+    /// Walking and writing in one pass; `total_files` counts the files selected so far and is
+    /// the real total only once `selection_complete` is set.
+    Writing,
+}
+
+/// Snapshot reported while an export runs. `current_path` is the file being written, if any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportProgress {
+    pub phase: ExportPhase,
+    pub files_processed: usize,
+    pub total_files: usize,
+    /// The walk has finished, so `total_files` no longer grows.
+    pub selection_complete: bool,
+    pub bytes_written: u64,
+    pub current_path: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanLimits {
+    pub max_files: usize,
+    pub max_depth: usize,
+}
+
+impl Default for ScanLimits {
+    fn default() -> Self {
+        Self {
+            max_files: 100_000,
+            max_depth: 64,
+        }
+    }
+}
+
+/// The desktop app's default config rooted at `root_path`, for tests to adjust field by field.
+/// The global excludes file is off, so the developer's own git config can't change results.
+#[cfg(test)]
+pub(crate) fn test_config(root_path: &str) -> ExportConfig {
+    ExportConfig {
+        root_path: root_path.to_string(),
+        use_gitignore: true,
+        include_globs: vec![],
+        exclude_globs: vec![],
+        include_extensions: vec![],
+        exclude_extensions: vec![],
+        structure_only: false,
+        max_file_size_kb: 256,
+        large_file_strategy: LargeFileStrategy::Truncate,
+        max_file_lines: None,
+        truncate_at_line_boundary: false,
+        size_overrides: vec![],
+        binary_policy: BinaryPolicy::Skip,
+        binary_dump_max_kb: default_binary_dump_max_kb(),
+        manual_selections: BTreeMap::new(),
+        output_format: OutputFormat::Txt,
+        tokenizer: TokenizerKind::Cl100k,
+        token_budget: None,
+        priority_globs: vec![],
+        overwrite_policy: OverwritePolicy::Overwrite,
+        ignore_sources: IgnoreSources {
+            global_excludes: false,
+            ..IgnoreSources::default()
+        },
+        hard_excludes: default_hard_excludes(),
+        secret_action: SecretAction::Warn,
+        encoding_overrides: vec![],
+        line_endings: LineEndingMode::Normalize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ExportConfig;
+
+    #[test]
+    fn export_config_accepts_max_file_size_kb_uppercase_key() {
+        let payload = json!({
+            "rootPath": "D:/repo",
+            "useGitignore": true,
+            "includeGlobs": [],
+            "excludeGlobs": [],
+            "includeExtensions": [],
+            "excludeExtensions": [],
+            "maxFileSizeKB": 256,
+            "largeFileStrategy": "truncate",
+            "manualSelections": {},
+            "outputFormat": "txt"
+        });
+
+        let config: ExportConfig = serde_json::from_value(payload).unwrap();
+        assert_eq!(config.max_file_size_kb, 256);
+    }
+
+    #[test]
+    fn export_config_accepts_legacy_max_file_size_kb_camel_case_key() {
+        let payload = json!({
+            "rootPath": "D:/repo",
+            "useGitignore": true,
+            "includeGlobs": [],
+            "excludeGlobs": [],
+            "includeExtensions": [],
+            "excludeExtensions": [],
+            "maxFileSizeKb": 128,
+            "largeFileStrategy": "truncate",
+            "manualSelections": {},
+            "outputFormat": "txt"
+        });
+
+        let config: ExportConfig = serde_json::from_value(payload).unwrap();
+        assert_eq!(config.max_file_size_kb, 128);
+    }
+}
@@ -1,3 +0,0 @@
-pub mod exporter;
-pub mod scanner;
-pub mod selection;
@@ -1,29 +1,192 @@
-use crate::application::exporter::{
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use codebase_to_txt_core::models::{
+    ExportConfig, ExportPhase, ExportProgress, ExportResult, PreviewMeta, ScanLimits,
+    SelectionSummary,
+};
+use codebase_to_txt_core::{
     evaluate_selection as evaluate_selection_impl, preview_export as preview_export_impl,
-    run_export as run_export_impl,
+    run_export_observed, AppError, ExportObserver,
 };
-use crate::models::{ExportConfig, ExportResult, PreviewMeta, ScanLimits, SelectionSummary};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 use super::{validate_output_path, validate_root_path};
 
+const EXPORT_PROGRESS_EVENT: &str = "export://progress";
+const EXPORT_FINISHED_EVENT: &str = "export://finished";
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Cancellation flags of the export jobs still running, keyed by job id.
+#[derive(Default)]
+pub struct ExportJobs {
+    next_id: AtomicU64,
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl ExportJobs {
+    fn running(&self) -> MutexGuard<'_, HashMap<String, Arc<AtomicBool>>> {
+        self.running.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressEvent<'a> {
+    job_id: &'a str,
+    #[serde(flatten)]
+    progress: &'a ExportProgress,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FinishedEvent {
+    job_id: String,
+    result: Option<ExportResult>,
+    error: Option<AppError>,
+}
+
+/// Forwards progress as events, at most one per `PROGRESS_EMIT_INTERVAL` plus the final one.
+struct JobObserver {
+    app: AppHandle,
+    job_id: String,
+    cancelled: Arc<AtomicBool>,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+impl ExportObserver for JobObserver {
+    fn on_progress(&self, progress: &ExportProgress) {
+        let finished = progress.phase == ExportPhase::Writing
+            && progress.selection_complete
+            && progress.files_processed == progress.total_files;
+        let mut last_emit = self
+            .last_emit
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !finished && last_emit.is_some_and(|at| at.elapsed() < PROGRESS_EMIT_INTERVAL) {
+            return;
+        }
+        *last_emit = Some(Instant::now());
+        let _ = self.app.emit(
+            EXPORT_PROGRESS_EVENT,
+            ProgressEvent {
+                job_id: &self.job_id,
+                progress,
+            },
+        );
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
 #[tauri::command]
-pub fn evaluate_selection(config: ExportConfig) -> Result<SelectionSummary, String> {
+pub fn evaluate_selection(config: ExportConfig) -> Result<SelectionSummary, AppError> {
     validate_root_path(&config.root_path)?;
     let limits = ScanLimits::default();
     evaluate_selection_impl(&config, &limits)
 }
 
+/// Walks, reads and tokenizes the whole selection, so it runs on the blocking pool rather than
+/// holding up the main thread.
 #[tauri::command]
-pub fn preview_export(config: ExportConfig) -> Result<PreviewMeta, String> {
+pub async fn preview_export(config: ExportConfig) -> Result<PreviewMeta, AppError> {
     validate_root_path(&config.root_path)?;
-    let limits = ScanLimits::default();
-    preview_export_impl(&config, &limits)
+    let preview = tauri::async_runtime::spawn_blocking(move || {
+        let limits = ScanLimits::default();
+        preview_export_impl(&config, &limits)
+    });
+    preview.await.map_err(|error| AppError::Internal {
+        context: "Preview task failed".to_string(),
+        reason: error.to_string(),
+    })?
 }
 
+/// Starts the export on a background thread and returns its job id. Progress arrives as
+/// `export://progress` events and the outcome as one `export://finished` event.
 #[tauri::command]
-pub fn run_export(config: ExportConfig, output_path: String) -> Result<ExportResult, String> {
+pub fn run_export(
+    app: AppHandle,
+    jobs: State<'_, ExportJobs>,
+    config: ExportConfig,
+    output_path: String,
+    confirm_sensitive: bool,
+) -> Result<String, AppError> {
     validate_root_path(&config.root_path)?;
     validate_output_path(&output_path)?;
-    let limits = ScanLimits::default();
-    run_export_impl(&config, &output_path, &limits)
+
+    let job_id = format!(
+        "export-{}",
+        jobs.next_id.fetch_add(1, Ordering::Relaxed) + 1
+    );
+    let cancelled = Arc::new(AtomicBool::new(false));
+    jobs.running()
+        .insert(job_id.clone(), Arc::clone(&cancelled));
+    let observer = JobObserver {
+        app,
+        job_id: job_id.clone(),
+        cancelled,
+        last_emit: Mutex::new(None),
+    };
+
+    thread::spawn(move || {
+        // A panic still has to release the job and settle the frontend's wait for it.
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            let limits = ScanLimits::default();
+            run_export_observed(&config, &output_path, &limits, confirm_sensitive, &observer)
+        }))
+        .unwrap_or_else(|payload| {
+            Err(AppError::Internal {
+                context: "Export job panicked".to_string(),
+                reason: panic_reason(payload.as_ref()),
+            })
+        });
+        observer
+            .app
+            .state::<ExportJobs>()
+            .running()
+            .remove(&observer.job_id);
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        let _ = observer.app.emit(
+            EXPORT_FINISHED_EVENT,
+            FinishedEvent {
+                job_id: observer.job_id.clone(),
+                result,
+                error,
+            },
+        );
+    });
+
+    Ok(job_id)
+}
+
+fn panic_reason(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|reason| reason.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Asks a running export to stop; it finishes with `E_EXPORT_CANCELLED` and removes its partial
+/// output. Returns false when the job already finished.
+#[tauri::command]
+pub fn cancel_export(jobs: State<'_, ExportJobs>, job_id: String) -> bool {
+    match jobs.running().get(&job_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
 }
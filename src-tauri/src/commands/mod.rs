@@ -1,21 +1,21 @@
 mod export;
 mod scan;
 
-use crate::infrastructure::errors::{coded, E_OUTPUT_REQUIRED, E_ROOT_REQUIRED};
+use codebase_to_txt_core::AppError;
 
-pub use export::{evaluate_selection, preview_export, run_export};
-pub use scan::{scan_children, scan_tree};
+pub use export::{cancel_export, evaluate_selection, preview_export, run_export, ExportJobs};
+pub use scan::{explain_path, scan_children, scan_tree};
 
-fn validate_root_path(root_path: &str) -> Result<(), String> {
+fn validate_root_path(root_path: &str) -> Result<(), AppError> {
     if root_path.trim().is_empty() {
-        return Err(coded(E_ROOT_REQUIRED, "rootPath is required"));
+        return Err(AppError::RootRequired);
     }
     Ok(())
 }
 
-fn validate_output_path(output_path: &str) -> Result<(), String> {
+fn validate_output_path(output_path: &str) -> Result<(), AppError> {
     if output_path.trim().is_empty() {
-        return Err(coded(E_OUTPUT_REQUIRED, "outputPath is required"));
+        return Err(AppError::OutputRequired);
     }
     Ok(())
 }
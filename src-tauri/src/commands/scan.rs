@@ -1,22 +1,28 @@
-use crate::application::scanner::{scan_children as scan_children_impl, scan_root};
-use crate::models::{ExportConfig, ScanLimits, TreeNode};
+use codebase_to_txt_core::models::{
+    DecisionExplanation, ExportConfig, ScanBatch, ScanLimits, ScanTree,
+};
+use codebase_to_txt_core::{
+    explain_path as explain_path_impl, scan_children as scan_children_impl, scan_root, AppError,
+};
 
 use super::validate_root_path;
 
 #[tauri::command]
-pub fn scan_tree(config: ExportConfig) -> Result<TreeNode, String> {
+pub fn scan_tree(config: ExportConfig) -> Result<ScanTree, AppError> {
     validate_root_path(&config.root_path)?;
     let limits = ScanLimits::default();
     scan_root(&config, &limits)
 }
 
 #[tauri::command]
-pub fn scan_children(
-    config: ExportConfig,
-    dir_path: String,
-) -> Result<Vec<TreeNode>, String> {
+pub fn scan_children(config: ExportConfig, dir_path: String) -> Result<ScanBatch, AppError> {
     validate_root_path(&config.root_path)?;
     let limits = ScanLimits::default();
-    let batch = scan_children_impl(&config, &dir_path, &limits)?;
-    Ok(batch.nodes)
+    scan_children_impl(&config, &dir_path, &limits)
+}
+
+#[tauri::command]
+pub fn explain_path(config: ExportConfig, path: String) -> Result<DecisionExplanation, AppError> {
+    validate_root_path(&config.root_path)?;
+    explain_path_impl(&config, &path)
 }
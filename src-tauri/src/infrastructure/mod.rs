@@ -1,4 +0,0 @@
-pub mod errors;
-pub mod fs_scan;
-pub mod pathing;
-pub mod sorting;
@@ -1,22 +1,24 @@
-mod application;
 mod commands;
-mod domain;
-mod infrastructure;
-mod models;
 
-use commands::{evaluate_selection, preview_export, run_export, scan_children, scan_tree};
+use commands::{
+    cancel_export, evaluate_selection, explain_path, preview_export, run_export, scan_children,
+    scan_tree, ExportJobs,
+};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
+        .manage(ExportJobs::default())
         .invoke_handler(tauri::generate_handler![
             scan_tree,
             scan_children,
+            explain_path,
             evaluate_selection,
             preview_export,
-            run_export
+            run_export,
+            cancel_export
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -3,6 +3,7 @@ import { Icon } from "@iconify/react";
 import { Tree } from "antd";
 import { getIconForFile, getIconForFolder, getIconForOpenFolder } from "vscode-icons-js";
 import type {
+  DecisionExplanation,
   ManualSelectionState,
   SelectionSummary,
   TreeNode,
@@ -12,15 +13,18 @@ type DirectoryPanelProps = {
   rootPath: string;
   busy: boolean;
   tree: TreeNode | null;
+  scanWarnings: string[];
   selectionSummary: SelectionSummary | null;
   expandedPaths: Set<string>;
   loadingPaths: Set<string>;
   manualSelections: Record<string, ManualSelectionState>;
+  explanation: DecisionExplanation | null;
   onRootPathChange: (nextPath: string) => void;
   onPickRootPath: () => Promise<void>;
   onScan: () => Promise<void>;
   onEvaluate: () => Promise<void>;
   onToggleNode: (node: TreeNode) => Promise<void>;
+  onExplainNode: (node: TreeNode) => Promise<void>;
   onSyncManualSelections: (
     checkedPaths: string[],
     changedPath: string,
@@ -40,15 +44,18 @@ export function DirectoryPanel({
   rootPath,
   busy,
   tree,
+  scanWarnings,
   selectionSummary,
   expandedPaths,
   loadingPaths,
   manualSelections,
+  explanation,
   onRootPathChange,
   onPickRootPath,
   onScan,
   onEvaluate,
   onToggleNode,
+  onExplainNode,
   onSyncManualSelections,
 }: DirectoryPanelProps) {
   const nodeLookup = useMemo(() => {
@@ -91,6 +98,19 @@ export function DirectoryPanel({
               {isLoading ? " (Loading...)" : ""}
             </span>
             {node.ignoredByGitignore ? <span className="tree-node-meta">gitignored</span> : null}
+            {node.path !== "." ? (
+              <button
+                className="tree-node-explain"
+                title="Why is this included or excluded?"
+                disabled={busy}
+                onClick={(event) => {
+                  event.stopPropagation();
+                  void onExplainNode(node);
+                }}
+              >
+                why?
+              </button>
+            ) : null}
           </div>
         ),
         children: node.children.map(toTreeDataNode),
@@ -98,7 +118,7 @@ export function DirectoryPanel({
     };
 
     return [toTreeDataNode(tree)];
-  }, [tree, loadingPaths, expandedPaths]);
+  }, [tree, loadingPaths, expandedPaths, busy, onExplainNode]);
 
   const checkedKeys = useMemo(
     () => {
@@ -175,12 +195,35 @@ export function DirectoryPanel({
               <p>Included files: {selectionSummary.includedFiles}</p>
               <p>Excluded files: {selectionSummary.excludedFiles}</p>
               <p className="meta">Warnings: {selectionSummary.warnings.join(" | ") || "None"}</p>
+              {selectionSummary.budget ? (
+                <p className="meta">
+                  Token budget {selectionSummary.budget.tokenBudget.toLocaleString()}: ~
+                  {selectionSummary.budget.estimatedTokens.toLocaleString()} tokens after{" "}
+                  {selectionSummary.budget.cuts.length} cuts
+                  {selectionSummary.budget.cuts.length
+                    ? ` (${selectionSummary.budget.cuts
+                        .map((cut) => `${cut.action} ${cut.path}`)
+                        .join(", ")})`
+                    : ""}
+                </p>
+              ) : null}
             </>
           ) : (
             <p className="meta">No evaluation yet.</p>
           )}
         </div>
 
+        {scanWarnings.length ? (
+          <div className="status-card">
+            <h3>Scan Warnings</h3>
+            {scanWarnings.map((warning) => (
+              <p key={warning} className="meta">
+                ⚠ {warning}
+              </p>
+            ))}
+          </div>
+        ) : null}
+
         <div className="tree-box">
           {tree ? (
             <Tree
@@ -210,6 +253,22 @@ export function DirectoryPanel({
             <p className="meta">No tree data yet. Run scan first.</p>
           )}
         </div>
+
+        {explanation ? (
+          <div className={`status-card${explanation.included ? " success" : ""}`}>
+            <h3>
+              {explanation.path} · {explanation.included ? "included" : "excluded"}
+            </h3>
+            <p>{explanation.summary}</p>
+            <p className="meta">
+              Rule: {explanation.rule}
+              {explanation.pattern ? ` · pattern ${explanation.pattern}` : ""}
+              {explanation.source
+                ? ` · ${explanation.source.file}${explanation.source.line ? `:${explanation.source.line}` : ""}`
+                : ""}
+            </p>
+          </div>
+        ) : null}
       </div>
     </section>
   );
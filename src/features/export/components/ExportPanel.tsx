@@ -1,37 +1,54 @@
 import type {
+  ExportProgress,
   ExportResult,
+  OverwritePolicy,
   PreviewMeta,
+  SecretAction,
   SelectionSummary,
 } from "../../../shared/types/export";
 
 type ExportPanelProps = {
   busy: boolean;
   outputPath: string;
+  overwritePolicy: OverwritePolicy;
+  secretAction: SecretAction;
   structureOnly: boolean;
   preview: PreviewMeta | null;
   exportResult: ExportResult | null;
+  exportProgress: ExportProgress | null;
+  canCancelExport: boolean;
   selectionSummary: SelectionSummary | null;
   errorMessage: string | null;
   onOutputPathChange: (nextPath: string) => void;
+  onOverwritePolicyChange: (nextPolicy: OverwritePolicy) => void;
+  onSecretActionChange: (nextAction: SecretAction) => void;
   onStructureOnlyChange: (nextValue: boolean) => void;
   onPickOutputPath: () => Promise<void>;
   onPreview: () => Promise<void>;
   onExport: () => Promise<void>;
+  onCancelExport: () => Promise<void>;
 };
 
 export function ExportPanel({
   busy,
   outputPath,
+  overwritePolicy,
+  secretAction,
   structureOnly,
   preview,
   exportResult,
+  exportProgress,
+  canCancelExport,
   selectionSummary,
   errorMessage,
   onOutputPathChange,
+  onOverwritePolicyChange,
+  onSecretActionChange,
   onStructureOnlyChange,
   onPickOutputPath,
   onPreview,
   onExport,
+  onCancelExport,
 }: ExportPanelProps) {
   return (
     <section className="panel">
@@ -52,6 +69,33 @@ export function ExportPanel({
           />
         </div>
 
+        <div className="field">
+          <label htmlFor="overwrite-policy">If Output Exists</label>
+          <select
+            id="overwrite-policy"
+            value={overwritePolicy}
+            onChange={(e) => onOverwritePolicyChange(e.currentTarget.value as OverwritePolicy)}
+          >
+            <option value="overwrite">overwrite</option>
+            <option value="fail_if_exists">fail</option>
+            <option value="auto_suffix">add numeric suffix</option>
+          </select>
+        </div>
+
+        <div className="field">
+          <label htmlFor="secret-action">Possible Secrets</label>
+          <select
+            id="secret-action"
+            value={secretAction}
+            onChange={(e) => onSecretActionChange(e.currentTarget.value as SecretAction)}
+          >
+            <option value="warn">warn</option>
+            <option value="redact">redact</option>
+            <option value="skip">skip file</option>
+            <option value="off">don't scan</option>
+          </select>
+        </div>
+
         <div className="actions">
           <button className="btn" onClick={() => void onPickOutputPath()} disabled={busy}>
             Browse
@@ -62,8 +106,31 @@ export function ExportPanel({
           <button className="btn primary" onClick={() => void onExport()} disabled={busy}>
             Export
           </button>
+          {canCancelExport && (
+            <button className="btn" onClick={() => void onCancelExport()}>
+              Cancel
+            </button>
+          )}
         </div>
 
+        {canCancelExport && (
+          <div className="status-card">
+            <h3>Exporting</h3>
+            {exportProgress?.phase === "writing" ? (
+              <p className="stat-label">
+                {formatFileProgress(exportProgress)} · {formatBytes(exportProgress.bytesWritten)}
+              </p>
+            ) : (
+              <p className="stat-label">
+                Selecting files{exportProgress ? ` · ${exportProgress.filesProcessed} checked` : ""}
+              </p>
+            )}
+            {exportProgress?.currentPath && (
+              <p className="meta" style={{ wordBreak: "break-all" }}>{exportProgress.currentPath}</p>
+            )}
+          </div>
+        )}
+
         <div className="field">
           <label htmlFor="structure-only">
             <input
@@ -83,7 +150,12 @@ export function ExportPanel({
             {preview ? (
               <>
                 <p className="stat-num">{preview.includedFiles}</p>
-                <p className="stat-label">files · {formatBytes(preview.estimatedBytes)}</p>
+                <p className="stat-label">
+                  files · {formatBytes(preview.estimatedBytes)}
+                  {preview.estimatedTokens !== null
+                    ? ` · ~${preview.estimatedTokens.toLocaleString()} tokens`
+                    : ""}
+                </p>
                 <p className="meta">{preview.warnings.length ? `⚠ ${preview.warnings[0]}` : "No warnings"}</p>
               </>
             ) : (
@@ -117,6 +189,14 @@ export function ExportPanel({
             {exportResult.skippedFiles > 0 && (
               <p className="meta">⚠ {exportResult.skippedFiles} skipped</p>
             )}
+            {exportResult.lineStats.length > 0 && (
+              <p className="meta">
+                {exportResult.lineStats.filter((file) => file.crlfLines + file.crLines > 0).length}{" "}
+                with CRLF/CR or mixed line endings ·{" "}
+                {exportResult.lineStats.filter((file) => file.trailingWhitespaceLines > 0).length}{" "}
+                with trailing whitespace
+              </p>
+            )}
           </div>
         )}
 
@@ -126,6 +206,14 @@ export function ExportPanel({
   );
 }
 
+/** The total is only final once the walk is done; until then it grows as files are selected. */
+function formatFileProgress(progress: ExportProgress): string {
+  if (progress.selectionComplete) {
+    return `${progress.filesProcessed} / ${progress.totalFiles} files`;
+  }
+  return `${progress.filesProcessed} files · ${progress.totalFiles} selected so far`;
+}
+
 function formatBytes(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
   if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
@@ -1,9 +1,15 @@
 ﻿import { useEffect, useState } from "react";
 import type {
+  BinaryPolicy,
+  EncodingOverride,
   ExportConfig,
+  IgnoreSources,
   LargeFileStrategy,
+  LineEndingMode,
   OutputFormat,
   RulesDraft,
+  SizeOverride,
+  TokenizerKind,
 } from "../../../shared/types/export";
 
 type RulesPanelProps = {
@@ -16,7 +22,16 @@ type RulesPanelProps = {
   onApplyRules: () => Promise<ExportConfig | null>;
 };
 
+/** `gitOnly` sources are read only together with .gitignore. */
+const IGNORE_SOURCE_OPTIONS: { key: keyof IgnoreSources; label: string; gitOnly: boolean }[] = [
+  { key: "dotIgnore", label: "Apply .ignore", gitOnly: false },
+  { key: "rgignore", label: "Apply .rgignore", gitOnly: false },
+  { key: "gitInfoExclude", label: "Apply .git/info/exclude", gitOnly: true },
+  { key: "globalExcludes", label: "Apply global git excludes (core.excludesFile)", gitOnly: true },
+];
+
 type ListField =
+  | "hardExcludes"
   | "includeGlobs"
   | "excludeGlobs"
   | "includeExtensions"
@@ -29,6 +44,55 @@ function parseCsv(rawValue: string): string[] {
     .filter((item) => item.length > 0);
 }
 
+function formatEncodingOverrides(overrides: EncodingOverride[]): string {
+  return overrides.map(({ glob, encoding }) => `${glob}=${encoding}`).join(", ");
+}
+
+/** "glob=encoding" pairs; entries without an encoding are dropped. */
+function parseEncodingOverrides(rawValue: string): EncodingOverride[] {
+  return parseCsv(rawValue).flatMap((item) => {
+    const separator = item.lastIndexOf("=");
+    const glob = item.slice(0, separator).trim();
+    const encoding = item.slice(separator + 1).trim();
+    return separator > 0 && glob && encoding ? [{ glob, encoding }] : [];
+  });
+}
+
+const LARGE_FILE_STRATEGIES: LargeFileStrategy[] = ["truncate", "head_tail", "skip", "keep"];
+
+function formatSizeOverrides(overrides: SizeOverride[]): string {
+  return overrides
+    .map(({ glob, maxFileSizeKB, largeFileStrategy }) => {
+      const spec = [maxFileSizeKB, largeFileStrategy].filter((part) => part !== null).join(":");
+      return `${glob}=${spec}`;
+    })
+    .join(", ");
+}
+
+/** "glob=KB", "glob=strategy" or "glob=KB:strategy"; entries setting neither are dropped. */
+function parseSizeOverrides(rawValue: string): SizeOverride[] {
+  return parseCsv(rawValue).flatMap((item) => {
+    const separator = item.lastIndexOf("=");
+    const glob = item.slice(0, separator).trim();
+    const entry: SizeOverride = {
+      glob,
+      maxFileSizeKB: null,
+      maxFileLines: null,
+      largeFileStrategy: null,
+    };
+    for (const part of item.slice(separator + 1).split(":").map((value) => value.trim())) {
+      const kb = Number(part);
+      if (part && Number.isInteger(kb) && kb >= 0) {
+        entry.maxFileSizeKB = kb;
+      } else if ((LARGE_FILE_STRATEGIES as string[]).includes(part)) {
+        entry.largeFileStrategy = part as LargeFileStrategy;
+      }
+    }
+    const valid = separator > 0 && glob && (entry.maxFileSizeKB !== null || entry.largeFileStrategy);
+    return valid ? [entry] : [];
+  });
+}
+
 export function RulesPanel({
   config,
   rulesDraft,
@@ -46,16 +110,29 @@ export function RulesPanel({
   const [excludeExtensionsText, setExcludeExtensionsText] = useState(() =>
     rulesDraft.excludeExtensions.join(", "),
   );
+  const [hardExcludesText, setHardExcludesText] = useState(() => rulesDraft.hardExcludes.join(", "));
+  const [priorityGlobsText, setPriorityGlobsText] = useState(() => config.priorityGlobs.join(", "));
+  const [sizeOverridesText, setSizeOverridesText] = useState(() =>
+    formatSizeOverrides(config.sizeOverrides),
+  );
+  const [encodingOverridesText, setEncodingOverridesText] = useState(() =>
+    formatEncodingOverrides(config.encodingOverrides),
+  );
 
   useEffect(() => {
     setIncludeGlobsText(rulesDraft.includeGlobs.join(", "));
     setExcludeGlobsText(rulesDraft.excludeGlobs.join(", "));
     setIncludeExtensionsText(rulesDraft.includeExtensions.join(", "));
     setExcludeExtensionsText(rulesDraft.excludeExtensions.join(", "));
+    setHardExcludesText(rulesDraft.hardExcludes.join(", "));
   }, [rulesDraft]);
 
   const updateListField = (field: ListField, rawValue: string) => {
     const nextList = parseCsv(rawValue);
+    if (field === "hardExcludes") {
+      onUpdateRulesDraft({ hardExcludes: nextList });
+      return;
+    }
     if (field === "includeGlobs") {
       onUpdateRulesDraft({ includeGlobs: nextList });
       return;
@@ -89,6 +166,26 @@ export function RulesPanel({
             />{" "}
             Apply .gitignore
           </label>
+          {IGNORE_SOURCE_OPTIONS.map(({ key, label, gitOnly }) => (
+            <label key={key} htmlFor={`ignore-source-${key}`}>
+              <input
+                id={`ignore-source-${key}`}
+                type="checkbox"
+                checked={rulesDraft.ignoreSources[key]}
+                disabled={gitOnly && !rulesDraft.useGitignore}
+                onChange={(event) =>
+                  onUpdateRulesDraft({
+                    ignoreSources: { ...rulesDraft.ignoreSources, [key]: event.currentTarget.checked },
+                  })
+                }
+              />{" "}
+              {label}
+            </label>
+          ))}
+          <p className="field-hint">
+            The git excludes are applied only together with .gitignore; .codebasetotxtignore files
+            are always applied.
+          </p>
         </div>
 
         <div className="field">
@@ -147,6 +244,24 @@ export function RulesPanel({
           />
         </div>
 
+        <div className="field">
+          <label htmlFor="hard-excludes">Hard Excludes (comma-separated)</label>
+          <input
+            id="hard-excludes"
+            value={hardExcludesText}
+            onChange={(event) => {
+              const raw = event.currentTarget.value;
+              setHardExcludesText(raw);
+              updateListField("hardExcludes", raw);
+            }}
+            placeholder="node_modules, target, *.lock"
+          />
+          <p className="field-hint">
+            Names match at any depth. Check an entry in the tree to include it anyway; .git is always
+            excluded.
+          </p>
+        </div>
+
         <div className="actions">
           <button
             className="btn primary"
@@ -183,8 +298,114 @@ export function RulesPanel({
             }
           >
             <option value="truncate">truncate</option>
+            <option value="head_tail">keep head and tail</option>
             <option value="skip">skip</option>
+            <option value="keep">keep whole</option>
           </select>
+          <label htmlFor="truncate-at-line-boundary">
+            <input
+              id="truncate-at-line-boundary"
+              type="checkbox"
+              checked={config.truncateAtLineBoundary}
+              onChange={(event) =>
+                onUpdateConfig({ truncateAtLineBoundary: event.currentTarget.checked })
+              }
+            />{" "}
+            Cut truncated files at a line boundary
+          </label>
+        </div>
+
+        <div className="field">
+          <label htmlFor="max-file-lines">Max File Lines (empty = no limit)</label>
+          <input
+            id="max-file-lines"
+            type="number"
+            min={1}
+            value={config.maxFileLines ?? ""}
+            onChange={(event) => {
+              const next = Number(event.currentTarget.value);
+              onUpdateConfig({ maxFileLines: next > 0 ? next : null });
+            }}
+            placeholder="2000"
+          />
+        </div>
+
+        <div className="field">
+          <label htmlFor="size-overrides">Size Overrides (glob=KB:strategy, comma-separated)</label>
+          <input
+            id="size-overrides"
+            value={sizeOverridesText}
+            onChange={(event) => {
+              const raw = event.currentTarget.value;
+              setSizeOverridesText(raw);
+              onUpdateConfig({ sizeOverrides: parseSizeOverrides(raw) });
+            }}
+            placeholder="*.json=20:skip, *.rs=keep, docs/**=8:truncate"
+          />
+          <p className="field-hint">
+            The first matching glob replaces the limits above for a file; the export notes name it.
+          </p>
+        </div>
+
+        <div className="field">
+          <label htmlFor="encoding-overrides">Encoding Overrides (glob=encoding, comma-separated)</label>
+          <input
+            id="encoding-overrides"
+            value={encodingOverridesText}
+            onChange={(event) => {
+              const raw = event.currentTarget.value;
+              setEncodingOverridesText(raw);
+              onUpdateConfig({ encodingOverrides: parseEncodingOverrides(raw) });
+            }}
+            placeholder="legacy/**=shift_jis, *.properties=latin1"
+          />
+          <p className="field-hint">Other files are detected from their byte order mark or content.</p>
+        </div>
+
+        <div className="field">
+          <label htmlFor="line-endings">Line Endings</label>
+          <select
+            id="line-endings"
+            value={config.lineEndings}
+            onChange={(event) =>
+              onUpdateConfig({ lineEndings: event.currentTarget.value as LineEndingMode })
+            }
+          >
+            <option value="normalize">convert to LF</option>
+            <option value="annotate">convert to LF and note original style</option>
+            <option value="preserve">keep as stored</option>
+          </select>
+        </div>
+
+        <div className="field">
+          <label htmlFor="binary-policy">Binary Files</label>
+          <select
+            id="binary-policy"
+            value={config.binaryPolicy}
+            onChange={(event) =>
+              onUpdateConfig({ binaryPolicy: event.currentTarget.value as BinaryPolicy })
+            }
+          >
+            <option value="skip">skip (images get a stub)</option>
+            <option value="list">list in structure</option>
+            <option value="metadata">size, type and SHA-256</option>
+            <option value="hex">metadata and hex dump</option>
+            <option value="base64">metadata and base64</option>
+          </select>
+        </div>
+
+        <div className="field">
+          <label htmlFor="binary-dump-max-kb">Binary Dump Max Size (KB)</label>
+          <input
+            id="binary-dump-max-kb"
+            type="number"
+            min={0}
+            value={config.binaryDumpMaxKB}
+            onChange={(event) =>
+              onUpdateConfig({ binaryDumpMaxKB: Math.max(0, Number(event.currentTarget.value) || 0) })
+            }
+          />
+          <p className="field-hint">Larger binary files get metadata instead of a hex or base64 dump.</p>
         </div>
 
         <div className="field">
@@ -200,6 +421,50 @@ export function RulesPanel({
             <option value="md">md</option>
           </select>
         </div>
+
+        <div className="field">
+          <label htmlFor="token-budget">Token Budget (empty = unlimited)</label>
+          <input
+            id="token-budget"
+            type="number"
+            min={1}
+            value={config.tokenBudget ?? ""}
+            onChange={(event) => {
+              const next = Number(event.currentTarget.value);
+              onUpdateConfig({ tokenBudget: next > 0 ? next : null });
+            }}
+            placeholder="120000"
+          />
+        </div>
+
+        <div className="field">
+          <label htmlFor="priority-globs">Budget Priority Globs (highest first)</label>
+          <input
+            id="priority-globs"
+            value={priorityGlobsText}
+            onChange={(event) => {
+              const raw = event.currentTarget.value;
+              setPriorityGlobsText(raw);
+              onUpdateConfig({ priorityGlobs: parseCsv(raw) });
+            }}
+            placeholder="src/core/**, src/**"
+          />
+        </div>
+
+        <div className="field">
+          <label htmlFor="tokenizer">Token Estimate</label>
+          <select
+            id="tokenizer"
+            value={config.tokenizer}
+            onChange={(event) =>
+              onUpdateConfig({ tokenizer: event.currentTarget.value as TokenizerKind })
+            }
+          >
+            <option value="cl100k">cl100k</option>
+            <option value="o200k">o200k</option>
+            <option value="heuristic">heuristic</option>
+          </select>
+        </div>
       </div>
     </section>
   );
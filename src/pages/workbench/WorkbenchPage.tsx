@@ -3,7 +3,9 @@ import { DirectoryPanel } from "../../features/explorer/components/DirectoryPane
 import { ExportPanel } from "../../features/export/components/ExportPanel";
 import { RulesPanel } from "../../features/rules/components/RulesPanel";
 import {
+  cancelExport,
   evaluateSelection,
+  explainPath,
   pickRootDirectory,
   pickExportPath,
   previewExport,
@@ -12,14 +14,19 @@ import {
   scanTree,
 } from "../../shared/api/tauriClient";
 import type {
+  BackendError,
+  DecisionExplanation,
+  ErrorCode,
   ExportConfig,
+  ExportProgress,
   ExportResult,
+  IgnoreSources,
   PreviewMeta,
   RulesDraft,
   SelectionSummary,
   TreeNode,
 } from "../../shared/types/export";
-import { defaultExportConfig } from "../../shared/types/export";
+import { defaultExportConfig, isBackendError } from "../../shared/types/export";
 
 export function WorkbenchPage() {
   const [config, setConfig] = useState<ExportConfig>(() => {
@@ -34,9 +41,13 @@ export function WorkbenchPage() {
   });
   const [rulesDraft, setRulesDraft] = useState<RulesDraft>(() => extractRulesDraft(config));
   const [tree, setTree] = useState<TreeNode | null>(null);
+  const [scanWarnings, setScanWarnings] = useState<string[]>([]);
   const [selectionSummary, setSelectionSummary] = useState<SelectionSummary | null>(null);
   const [preview, setPreview] = useState<PreviewMeta | null>(null);
+  const [explanation, setExplanation] = useState<DecisionExplanation | null>(null);
   const [exportResult, setExportResult] = useState<ExportResult | null>(null);
+  const [exportJobId, setExportJobId] = useState<string | null>(null);
+  const [exportProgress, setExportProgress] = useState<ExportProgress | null>(null);
   const [outputPath, setOutputPath] = useState(() => buildDefaultOutputPath(config.rootPath));
   const [pendingAction, setPendingAction] = useState<string | null>(null);
   const [errorMessage, setErrorMessage] = useState<string | null>(null);
@@ -52,8 +63,7 @@ export function WorkbenchPage() {
     try {
       return await action();
     } catch (error) {
-      const text = error instanceof Error ? error.message : String(error);
-      setErrorMessage(formatBackendError(text));
+      setErrorMessage(formatBackendError(error));
       return null;
     } finally {
       setPendingAction(null);
@@ -84,11 +94,13 @@ export function WorkbenchPage() {
       manualSelections: {},
     }));
     setTree(null);
+    setScanWarnings([]);
     setSelectionSummary(null);
     setPreview(null);
     setExportResult(null);
     setExpandedPaths(new Set());
     setLoadingPaths(new Set());
+    setExplanation(null);
     setErrorMessage(null);
     setOutputPath(buildDefaultOutputPath(nextPath));
   };
@@ -106,12 +118,20 @@ export function WorkbenchPage() {
   const handleScan = async () => {
     const result = await runAction("scan", async () => scanTree(config));
     if (result) {
-      setTree(result);
+      setTree(result.root);
+      setScanWarnings(result.warnings);
       setExpandedPaths(new Set(["."]));
       setLoadingPaths(new Set());
     }
   };
 
+  const handleExplainNode = async (node: TreeNode) => {
+    const result = await runAction("explain", async () => explainPath(config, node.path));
+    if (result) {
+      setExplanation(result);
+    }
+  };
+
   const handleToggleNode = async (node: TreeNode) => {
     if (!node.isDir) {
       return;
@@ -134,7 +154,8 @@ export function WorkbenchPage() {
 
     setLoadingPaths((previous) => new Set(previous).add(node.path));
     try {
-      const children = await scanChildren(config, node.path);
+      const { nodes: children, warnings } = await scanChildren(config, node.path);
+      setScanWarnings((previous) => mergeWarnings(previous, warnings));
       setTree((previous) => {
         if (!previous) {
           return previous;
@@ -146,8 +167,7 @@ export function WorkbenchPage() {
         }));
       });
     } catch (error) {
-      const text = error instanceof Error ? error.message : String(error);
-      setErrorMessage(formatBackendError(text));
+      setErrorMessage(formatBackendError(error));
     } finally {
       setLoadingPaths((previous) => {
         const next = new Set(previous);
@@ -177,7 +197,8 @@ export function WorkbenchPage() {
 
     setConfig(nextConfig);
     setRulesDraft(extractRulesDraft(nextConfig));
-    setTree(result.nextTree);
+    setTree(result.nextTree.root);
+    setScanWarnings(result.nextTree.warnings);
     setSelectionSummary(result.nextSummary);
     setExpandedPaths(new Set(["."]));
     setLoadingPaths(new Set());
@@ -267,12 +288,36 @@ export function WorkbenchPage() {
       return;
     }
 
-    const result = await runAction("export", async () => runExport(activeConfig, outputPath.trim()));
+    const exportOptions = { onStarted: setExportJobId, onProgress: setExportProgress };
+    setExportProgress(null);
+    const result = await runAction("export", async () => {
+      try {
+        return await runExport(activeConfig, outputPath.trim(), exportOptions);
+      } catch (error) {
+        if (!isBackendError(error) || error.code !== "E_SENSITIVE_UNCONFIRMED") {
+          throw error;
+        }
+        const paths = (error.details.paths ?? []).join("\n");
+        if (!window.confirm(`These sensitive files are selected for export:\n${paths}\n\nExport them anyway?`)) {
+          throw error;
+        }
+        return runExport(activeConfig, outputPath.trim(), { ...exportOptions, confirmSensitive: true });
+      } finally {
+        setExportJobId(null);
+      }
+    });
+    setExportProgress(null);
     if (result) {
       setExportResult(result);
     }
   };
 
+  const handleCancelExport = async () => {
+    if (exportJobId) {
+      await cancelExport(exportJobId);
+    }
+  };
+
   const handlePickOutputPath = async () => {
     const pickedPath = await runAction("pick-path", async () => pickExportPath(outputPath));
     if (pickedPath) {
@@ -292,6 +337,7 @@ export function WorkbenchPage() {
         rootPath={config.rootPath}
         busy={busy}
         tree={tree}
+        scanWarnings={scanWarnings}
         selectionSummary={selectionSummary}
         expandedPaths={expandedPaths}
         loadingPaths={loadingPaths}
@@ -300,7 +346,9 @@ export function WorkbenchPage() {
         onPickRootPath={handlePickRootPath}
         onScan={handleScan}
         onEvaluate={handleEvaluate}
+        explanation={explanation}
         onToggleNode={handleToggleNode}
+        onExplainNode={handleExplainNode}
         onSyncManualSelections={handleSyncManualSelections}
       />
       <RulesPanel
@@ -315,16 +363,23 @@ export function WorkbenchPage() {
       <ExportPanel
         busy={busy}
         outputPath={outputPath}
+        overwritePolicy={config.overwritePolicy}
+        secretAction={config.secretAction}
         structureOnly={config.structureOnly}
         preview={preview}
         exportResult={exportResult}
+        exportProgress={exportProgress}
+        canCancelExport={exportJobId !== null}
         selectionSummary={selectionSummary}
         errorMessage={errorMessage}
         onOutputPathChange={setOutputPath}
+        onOverwritePolicyChange={(overwritePolicy) => updateConfig({ overwritePolicy })}
+        onSecretActionChange={(secretAction) => updateConfig({ secretAction })}
         onStructureOnlyChange={handleStructureOnlyChange}
         onPickOutputPath={handlePickOutputPath}
         onPreview={handlePreview}
         onExport={handleExport}
+        onCancelExport={handleCancelExport}
       />
     </main>
   );
@@ -333,6 +388,8 @@ export function WorkbenchPage() {
 function extractRulesDraft(config: ExportConfig): RulesDraft {
   return {
     useGitignore: config.useGitignore,
+    ignoreSources: { ...config.ignoreSources },
+    hardExcludes: [...config.hardExcludes],
     includeGlobs: [...config.includeGlobs],
     excludeGlobs: [...config.excludeGlobs],
     includeExtensions: [...config.includeExtensions],
@@ -343,6 +400,8 @@ function extractRulesDraft(config: ExportConfig): RulesDraft {
 function areRulesDraftEqual(left: RulesDraft, right: RulesDraft): boolean {
   return (
     left.useGitignore === right.useGitignore &&
+    areIgnoreSourcesEqual(left.ignoreSources, right.ignoreSources) &&
+    areStringListsEqual(left.hardExcludes, right.hardExcludes) &&
     areStringListsEqual(left.includeGlobs, right.includeGlobs) &&
     areStringListsEqual(left.excludeGlobs, right.excludeGlobs) &&
     areStringListsEqual(left.includeExtensions, right.includeExtensions) &&
@@ -350,6 +409,20 @@ function areRulesDraftEqual(left: RulesDraft, right: RulesDraft): boolean {
   );
 }
 
+function mergeWarnings(current: string[], incoming: string[]): string[] {
+  const fresh = incoming.filter((warning) => !current.includes(warning));
+  return fresh.length ? [...current, ...fresh] : current;
+}
+
+function areIgnoreSourcesEqual(left: IgnoreSources, right: IgnoreSources): boolean {
+  return (
+    left.dotIgnore === right.dotIgnore &&
+    left.rgignore === right.rgignore &&
+    left.gitInfoExclude === right.gitInfoExclude &&
+    left.globalExcludes === right.globalExcludes
+  );
+}
+
 function areStringListsEqual(left: string[], right: string[]): boolean {
   if (left.length !== right.length) {
     return false;
@@ -362,24 +435,32 @@ function areStringListsEqual(left: string[], right: string[]): boolean {
   return true;
 }
 
-function formatBackendError(raw: string): string {
-  const parsed = parseCodedError(raw);
-  if (!parsed) {
-    return raw;
+function formatBackendError(error: unknown): string {
+  if (!isBackendError(error)) {
+    return error instanceof Error ? error.message : String(error);
   }
-  const friendly = ERROR_CODE_MESSAGES[parsed.code] ?? parsed.message ?? "Operation failed.";
-  return `${friendly} [debug: ${raw}]`;
+  const friendly = ERROR_CODE_MESSAGES[error.code] ?? error.message ?? "Operation failed.";
+  const remediation = describeErrorDetails(error);
+  return `${friendly}${remediation ? ` ${remediation}` : ""} [debug: ${error.code} ${error.message}]`;
 }
 
-function parseCodedError(raw: string): { code: string; message: string } | null {
-  const match = raw.trim().match(/^\[(E_[A-Z_]+)\]\s*(.*)$/);
-  if (!match) {
-    return null;
+function describeErrorDetails({ code, details }: BackendError): string | null {
+  if (details.glob) {
+    return `Fix or remove the glob "${details.glob}".`;
+  }
+  if (details.ioKind === "PermissionDenied") {
+    return details.path ? `Permission denied for "${details.path}".` : "Permission denied.";
+  }
+  if (details.ioKind === "NotFound" && details.path) {
+    return `"${details.path}" was not found.`;
+  }
+  if (details.path && (code === "E_OUTPUT_IS_DIR" || code === "E_OUTPUT_EXISTS")) {
+    return `Choose a different output file than "${details.path}".`;
   }
-  return { code: match[1], message: match[2] ?? "" };
+  return null;
 }
 
-const ERROR_CODE_MESSAGES: Record<string, string> = {
+const ERROR_CODE_MESSAGES: Record<ErrorCode, string> = {
   E_ROOT_REQUIRED: "Root path is required.",
   E_ROOT_INVALID: "Root path does not exist or cannot be resolved.",
   E_ROOT_NOT_DIR: "Root path must be a directory.",
@@ -391,6 +472,10 @@ const ERROR_CODE_MESSAGES: Record<string, string> = {
   E_IO_READ: "Read failed while scanning or exporting files.",
   E_IO_WRITE: "Write failed while creating export output. Check file path and write permissions.",
   E_RULE_INVALID_GLOB: "One or more glob rules are invalid.",
+  E_SENSITIVE_UNCONFIRMED: "Export cancelled: sensitive files were not confirmed.",
+  E_EXPORT_CANCELLED: "Export cancelled. No output file was written.",
+  E_ENCODING_UNKNOWN: "An encoding override names an unknown encoding.",
+  E_INTERNAL: "An unexpected internal error occurred.",
 };
 
 function buildDefaultOutputPath(rootPath: string): string {
@@ -1,21 +1,33 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type {
+  DecisionExplanation,
   ExportConfig,
+  ExportFinished,
+  ExportProgress,
   ExportResult,
   PreviewMeta,
+  ScanBatch,
+  ScanTree,
   SelectionSummary,
-  TreeNode,
 } from "../types/export";
 
-export async function scanTree(config: ExportConfig): Promise<TreeNode> {
-  return invoke<TreeNode>("scan_tree", { config });
+export async function scanTree(config: ExportConfig): Promise<ScanTree> {
+  return invoke<ScanTree>("scan_tree", { config });
 }
 
 export async function scanChildren(
   config: ExportConfig,
   dirPath: string,
-): Promise<TreeNode[]> {
-  return invoke<TreeNode[]>("scan_children", { config, dirPath });
+): Promise<ScanBatch> {
+  return invoke<ScanBatch>("scan_children", { config, dirPath });
+}
+
+export async function explainPath(
+  config: ExportConfig,
+  path: string,
+): Promise<DecisionExplanation> {
+  return invoke<DecisionExplanation>("explain_path", { config, path });
 }
 
 export async function evaluateSelection(
@@ -28,18 +40,65 @@ export async function previewExport(config: ExportConfig): Promise<PreviewMeta>
   return invoke<PreviewMeta>("preview_export", { config });
 }
 
+export type RunExportOptions = {
+  confirmSensitive?: boolean;
+  onStarted?: (jobId: string) => void;
+  onProgress?: (progress: ExportProgress) => void;
+};
+
+/** Starts a background export job and resolves with its result once it finishes. */
 export async function runExport(
   config: ExportConfig,
   outputPath: string,
+  { confirmSensitive = false, onStarted, onProgress }: RunExportOptions = {},
 ): Promise<ExportResult> {
-  return invoke<ExportResult>("run_export", { config, outputPath });
+  let jobId: string | null = null;
+  // The job can finish before `run_export` returns its id, so finished events are kept by id.
+  const finished = new Map<string, ExportFinished>();
+  let wake: (() => void) | null = null;
+  const unlisten = await Promise.all([
+    listen<ExportProgress>("export://progress", ({ payload }) => {
+      if (payload.jobId === jobId) {
+        onProgress?.(payload);
+      }
+    }),
+    listen<ExportFinished>("export://finished", ({ payload }) => {
+      finished.set(payload.jobId, payload);
+      wake?.();
+    }),
+  ]);
+
+  try {
+    const startedId = await invoke<string>("run_export", { config, outputPath, confirmSensitive });
+    jobId = startedId;
+    onStarted?.(startedId);
+    while (!finished.has(startedId)) {
+      await new Promise<void>((resolve) => {
+        wake = resolve;
+      });
+    }
+    const outcome = finished.get(startedId)!;
+    if (outcome.error) {
+      throw outcome.error;
+    }
+    return outcome.result!;
+  } finally {
+    unlisten.forEach((stop) => stop());
+  }
+}
+
+export async function cancelExport(jobId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_export", { jobId });
 }
 
 export async function pickExportPath(defaultPath?: string): Promise<string | null> {
   return invoke<string | null>("plugin:dialog|save", {
     options: {
       defaultPath: defaultPath?.trim() ? defaultPath.trim() : undefined,
-      filters: [{ name: "Text", extensions: ["txt"] }],
+      filters: [
+        { name: "Text", extensions: ["txt"] },
+        { name: "Markdown", extensions: ["md"] },
+      ],
     },
   });
 }
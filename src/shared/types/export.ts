@@ -1,6 +1,41 @@
-export type LargeFileStrategy = "truncate" | "skip";
+/** "head_tail" keeps the start and end of a large file around a marker counting the omitted lines. */
+export type LargeFileStrategy = "truncate" | "head_tail" | "skip" | "keep";
 export type OutputFormat = "txt" | "md";
 export type ManualSelectionState = "include" | "exclude" | "inherit";
+export type TokenizerKind = "cl100k" | "o200k" | "heuristic";
+export type OverwritePolicy = "overwrite" | "fail_if_exists" | "auto_suffix";
+export type SecretAction = "off" | "warn" | "redact" | "skip";
+/** "annotate" converts to LF like "normalize" and notes each file that had other endings. */
+export type LineEndingMode = "normalize" | "annotate" | "preserve";
+export type LineEndingStyle = "lf" | "crlf" | "cr" | "mixed";
+/**
+ * "metadata" writes size, type and SHA-256; "hex" and "base64" add a dump of small files.
+ * PNG, JPEG, GIF and WebP images also get their dimensions under every policy but "skip".
+ */
+export type BinaryPolicy = "skip" | "list" | "metadata" | "hex" | "base64";
+
+/** Limits for files matching `glob`; null fields keep the global setting. First match wins. */
+export interface SizeOverride {
+  glob: string;
+  maxFileSizeKB: number | null;
+  maxFileLines: number | null;
+  largeFileStrategy: LargeFileStrategy | null;
+}
+
+/** Files matching `glob` are read as `encoding` (e.g. "shift_jis", "utf-16le"); first match wins. */
+export interface EncodingOverride {
+  glob: string;
+  encoding: string;
+}
+
+/** Ignore files read besides .gitignore; .codebasetotxtignore is always read. */
+export interface IgnoreSources {
+  dotIgnore: boolean;
+  rgignore: boolean;
+  /** Read only when `useGitignore` is on, like `globalExcludes`. */
+  gitInfoExclude: boolean;
+  globalExcludes: boolean;
+}
 
 export interface ExportConfig {
   rootPath: string;
@@ -12,13 +47,36 @@ export interface ExportConfig {
   structureOnly: boolean;
   maxFileSizeKB: number;
   largeFileStrategy: LargeFileStrategy;
+  /** Files with more lines than this are large too. */
+  maxFileLines: number | null;
+  /** Head-only truncation ends on the last whole line. */
+  truncateAtLineBoundary: boolean;
+  sizeOverrides: SizeOverride[];
   manualSelections: Record<string, ManualSelectionState>;
   outputFormat: OutputFormat;
+  tokenizer: TokenizerKind;
+  tokenBudget: number | null;
+  priorityGlobs: string[];
+  overwritePolicy: OverwritePolicy;
+  ignoreSources: IgnoreSources;
+  hardExcludes: string[];
+  secretAction: SecretAction;
+  encodingOverrides: EncodingOverride[];
+  lineEndings: LineEndingMode;
+  binaryPolicy: BinaryPolicy;
+  /** Larger binary files get metadata only under "hex" and "base64". */
+  binaryDumpMaxKB: number;
 }
 
 export type RulesDraft = Pick<
   ExportConfig,
-  "useGitignore" | "includeGlobs" | "excludeGlobs" | "includeExtensions" | "excludeExtensions"
+  "useGitignore"
+  | "ignoreSources"
+  | "hardExcludes"
+  | "includeGlobs"
+  | "excludeGlobs"
+  | "includeExtensions"
+  | "excludeExtensions"
 >;
 
 export interface TreeNode {
@@ -31,25 +89,162 @@ export interface TreeNode {
   children: TreeNode[];
 }
 
+export interface ScanTree {
+  root: TreeNode;
+  warnings: string[];
+}
+
+export interface ScanBatch {
+  nodes: TreeNode[];
+  warnings: string[];
+}
+
+export type DecisionRule =
+  | "hard_exclude"
+  | "hard_exclude_list"
+  | "sensitive_file"
+  | "manual_include"
+  | "manual_exclude"
+  | "include_glob_mismatch"
+  | "include_extension_mismatch"
+  | "include_glob"
+  | "include_extension"
+  | "exclude_extension"
+  | "exclude_glob"
+  | "gitignore"
+  | "gitignore_negation"
+  | "default";
+
+export interface RuleSource {
+  file: string;
+  line: number | null;
+}
+
+export interface DecisionExplanation {
+  path: string;
+  isDir: boolean;
+  included: boolean;
+  rule: DecisionRule;
+  pattern: string | null;
+  source: RuleSource | null;
+  summary: string;
+}
+
 export interface SelectionSummary {
   includedFiles: number;
   excludedFiles: number;
   warnings: string[];
+  budget: BudgetReport | null;
+}
+
+export type BudgetAction = "dropped" | "truncated";
+
+export interface BudgetCut {
+  path: string;
+  action: BudgetAction;
+  tokensBefore: number;
+  tokensAfter: number;
+  reason: string;
+}
+
+export interface BudgetReport {
+  tokenBudget: number;
+  estimatedTokens: number;
+  withinBudget: boolean;
+  cuts: BudgetCut[];
 }
 
 export interface PreviewMeta {
   includedFiles: number;
   estimatedBytes: number;
   estimatedTokens: number | null;
+  fileTokens: PathTokenCount[];
+  directoryTokens: PathTokenCount[];
   warnings: string[];
 }
 
+export interface PathTokenCount {
+  path: string;
+  tokens: number;
+}
+
 export interface ExportResult {
   outputPath: string;
   exportedFiles: number;
   skippedFiles: number;
   totalBytesWritten: number;
   notes: string[];
+  /** Files with CRLF, CR or mixed endings or trailing whitespace; counts are as stored. */
+  lineStats: FileLineStats[];
+}
+
+export interface FileLineStats {
+  path: string;
+  lineEndings: LineEndingStyle | null;
+  lfLines: number;
+  crlfLines: number;
+  crLines: number;
+  trailingWhitespaceLines: number;
+}
+
+export type ExportPhase = "selecting" | "writing";
+
+/**
+ * Emitted as `export://progress`. `totalFiles` is 0 while selecting and counts the files
+ * selected so far while writing, since the walk and the writer run together; it is the real
+ * total once `selectionComplete` is set.
+ */
+export interface ExportProgress {
+  jobId: string;
+  phase: ExportPhase;
+  filesProcessed: number;
+  totalFiles: number;
+  selectionComplete: boolean;
+  bytesWritten: number;
+  currentPath: string | null;
+}
+
+/** Emitted once as `export://finished`; exactly one of `result` and `error` is set. */
+export interface ExportFinished {
+  jobId: string;
+  result: ExportResult | null;
+  error: BackendError | null;
+}
+
+export type ErrorCode =
+  | "E_ROOT_REQUIRED"
+  | "E_ROOT_INVALID"
+  | "E_ROOT_NOT_DIR"
+  | "E_PATH_OUTSIDE_ROOT"
+  | "E_DIRPATH_NOT_DIR"
+  | "E_OUTPUT_REQUIRED"
+  | "E_OUTPUT_IS_DIR"
+  | "E_OUTPUT_EXISTS"
+  | "E_IO_READ"
+  | "E_IO_WRITE"
+  | "E_RULE_INVALID_GLOB"
+  | "E_SENSITIVE_UNCONFIRMED"
+  | "E_EXPORT_CANCELLED"
+  | "E_ENCODING_UNKNOWN"
+  | "E_INTERNAL";
+
+export interface BackendError {
+  code: ErrorCode;
+  message: string;
+  details: {
+    path?: string;
+    glob?: string;
+    ioKind?: string;
+    paths?: string[];
+  };
+}
+
+export function isBackendError(value: unknown): value is BackendError {
+  if (typeof value !== "object" || value === null) {
+    return false;
+  }
+  const candidate = value as Partial<BackendError>;
+  return typeof candidate.code === "string" && typeof candidate.message === "string";
 }
 
 export const defaultExportConfig: ExportConfig = {
@@ -62,6 +257,41 @@ export const defaultExportConfig: ExportConfig = {
   structureOnly: false,
   maxFileSizeKB: 256,
   largeFileStrategy: "truncate",
+  maxFileLines: null,
+  truncateAtLineBoundary: false,
+  sizeOverrides: [],
   manualSelections: {},
   outputFormat: "txt",
+  tokenizer: "cl100k",
+  tokenBudget: null,
+  priorityGlobs: [],
+  overwritePolicy: "overwrite",
+  secretAction: "warn",
+  encodingOverrides: [],
+  lineEndings: "normalize",
+  binaryPolicy: "skip",
+  binaryDumpMaxKB: 4,
+  ignoreSources: {
+    dotIgnore: true,
+    rgignore: true,
+    gitInfoExclude: true,
+    globalExcludes: true,
+  },
+  hardExcludes: [
+    "node_modules",
+    "target",
+    "dist",
+    ".venv",
+    "__pycache__",
+    ".idea",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "Cargo.lock",
+    "poetry.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "composer.lock",
+  ],
 };